@@ -1,27 +1,135 @@
+use crate::config::Config;
 use crate::handlers;
-use crate::sort;
+use crate::keymap::{self, Action, Keymap};
+use crate::theme::Theme;
+use crate::ui;
 use crossterm::event;
 use std::io;
+use std::time::Duration;
+use term_sort::complexity;
+use term_sort::data::{self, Distribution};
+use term_sort::metrics::MetricsHistory;
+use term_sort::registry::{self, Algorithm};
+use term_sort::script::{self, Script, ScriptSort};
+use term_sort::sort;
+use tui::layout::Rect;
 use tui::widgets::ListState;
 
 #[derive(Clone, Copy)]
 pub enum View {
     Menu,
-    Insertion,
-    Selection,
-    Bubble,
-    Shell,
-    Merge,
-    Heap,
-    Quick,
-    Quick3,
+    Sort(&'static Algorithm),
+    Script(&'static Script),
+    Complexity,
+    Settings,
+    /// The items of the current sort, being edited before it starts.
+    Edit,
+}
+
+impl View {
+    /// The actions the view's keys are looked up among, which the help
+    /// overlay lists.
+    pub fn actions(&self) -> &'static [Action] {
+        match self {
+            View::Menu => keymap::MENU,
+            View::Sort(_) | View::Script(_) => keymap::SORT,
+            View::Complexity => keymap::COMPLEXITY,
+            View::Settings => keymap::SETTINGS,
+            View::Edit => keymap::EDIT,
+        }
+    }
+
+    /// The properties of the algorithm the view runs, if it runs one.
+    pub fn info(&self) -> Option<&'static sort::Info> {
+        match self {
+            View::Sort(algorithm) => Some(algorithm.info),
+            View::Script(_) => Some(&script::INFO),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RenderStyle {
+    Bars,
+    Scatter,
+    HueStrip,
+    DisparityCircle,
+    WireDiagram,
+}
+
+impl RenderStyle {
+    pub const ALL: [RenderStyle; 5] = [
+        RenderStyle::Bars,
+        RenderStyle::Scatter,
+        RenderStyle::HueStrip,
+        RenderStyle::DisparityCircle,
+        RenderStyle::WireDiagram,
+    ];
+
+    pub fn next(&self) -> RenderStyle {
+        match self {
+            RenderStyle::Bars => RenderStyle::Scatter,
+            RenderStyle::Scatter => RenderStyle::HueStrip,
+            RenderStyle::HueStrip => RenderStyle::DisparityCircle,
+            RenderStyle::DisparityCircle => RenderStyle::WireDiagram,
+            RenderStyle::WireDiagram => RenderStyle::Bars,
+        }
+    }
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            RenderStyle::Bars => "Bars",
+            RenderStyle::Scatter => "Scatter Plot",
+            RenderStyle::HueStrip => "Hue Strip",
+            RenderStyle::DisparityCircle => "Disparity Circle",
+            RenderStyle::WireDiagram => "Wire Diagram",
+        }
+    }
+}
+
+/// The entry of `all` after `current`, or before it when not `forward`,
+/// wrapping around at either end.
+pub fn cycle<T: Copy + PartialEq>(all: &[T], current: T, forward: bool) -> T {
+    let index = all.iter().position(|item| *item == current).unwrap_or(0);
+    let step = if forward { 1 } else { all.len() - 1 };
+    all[(index + step) % all.len()]
 }
 
 pub struct App {
     pub current_view: View,
     pub states: AppStates,
+    /// How many bars fit across a chart with no panel beside it.
     pub ui_width: u16,
+    pub render_style: RenderStyle,
     pub sort: Option<Box<dyn sort::Sort>>,
+    pub metrics_history: MetricsHistory,
+    /// The number of items to sort, or one per column when unset.
+    pub size: Option<usize>,
+    pub distribution: Distribution,
+    pub seed: Option<u64>,
+    pub speed: Duration,
+    pub theme: Theme,
+    /// The presets the settings cycle through, with the configured theme
+    /// standing in for the preset it was built on.
+    pub themes: Vec<Theme>,
+    pub keymap: Keymap,
+    pub show_help: bool,
+    pub areas: MouseAreas,
+    /// The bar clicked on, whose value and starting index are shown.
+    pub inspected: Option<usize>,
+    /// Whether the timeline was pressed, so dragging carries on seeking.
+    pub seeking: bool,
+}
+
+/// Where the last frame drew the parts of the screen the mouse can click.
+#[derive(Default)]
+pub struct MouseAreas {
+    /// The menu entries, one per row from the first one shown.
+    pub menu: Rect,
+    /// The inside of the bars chart, and the columns between its bars.
+    pub bars: Option<(Rect, u16)>,
+    /// The row of the timeline that stands for the steps.
+    pub timeline: Rect,
 }
 
 pub struct StatefulList<T> {
@@ -67,21 +175,200 @@ impl<T> StatefulList<T> {
     }
 }
 
+/// The orders the menu can list the algorithms in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MenuOrder {
+    Default,
+    Name,
+    Best,
+    Average,
+    Worst,
+    Space,
+}
+
+impl MenuOrder {
+    pub fn next(&self) -> MenuOrder {
+        match self {
+            MenuOrder::Default => MenuOrder::Name,
+            MenuOrder::Name => MenuOrder::Best,
+            MenuOrder::Best => MenuOrder::Average,
+            MenuOrder::Average => MenuOrder::Worst,
+            MenuOrder::Worst => MenuOrder::Space,
+            MenuOrder::Space => MenuOrder::Default,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            MenuOrder::Default => "default",
+            MenuOrder::Name => "name",
+            MenuOrder::Best => "best time",
+            MenuOrder::Average => "average time",
+            MenuOrder::Worst => "worst time",
+            MenuOrder::Space => "space",
+        }
+    }
+}
+
 pub struct MenuState {
+    /// Every entry in its default order; `list` holds those shown.
+    pub all: Vec<(&'static str, View)>,
     pub list: StatefulList<(&'static str, View)>,
+    /// The first entry shown, once the list is too long to show whole.
+    pub offset: usize,
+    pub show_info: bool,
+    pub filter: Option<sort::Property>,
+    pub order: MenuOrder,
 }
 
-impl<'a> MenuState {
+impl Default for MenuState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MenuState {
     pub fn new() -> MenuState {
-        let list = StatefulList::default()
-            .set_items(vec![
-                ("Insertion Sort", View::Insertion),
-                ("Selection Sort", View::Selection),
-                ("Bubble Sort", View::Bubble),
-            ])
-            .initial_select(0);
-        MenuState { list }
+        let all = registry::ALGORITHMS
+            .iter()
+            .map(|algorithm| (algorithm.name, View::Sort(algorithm)))
+            .chain([("Empirical Complexity", View::Complexity)])
+            .collect::<Vec<_>>();
+        MenuState {
+            list: StatefulList::default()
+                .set_items(all.clone())
+                .initial_select(0),
+            all,
+            offset: 0,
+            show_info: false,
+            filter: None,
+            order: MenuOrder::Default,
+        }
     }
+
+    /// Lists a loaded script after the built-in algorithms.
+    pub fn add_script(&mut self, script: &'static Script) {
+        let position = self.all.len() - 1;
+        self.all
+            .insert(position, (script.name.as_str(), View::Script(script)));
+        self.refresh();
+    }
+
+    pub fn selected(&self) -> Option<(&'static str, View)> {
+        self.list
+            .state
+            .selected()
+            .and_then(|selected| self.list.items.get(selected).copied())
+    }
+
+    pub fn cycle_filter(&mut self) {
+        self.filter = match self.filter {
+            None => Some(sort::Property::ALL[0]),
+            Some(property) => sort::Property::ALL
+                .iter()
+                .skip_while(|p| **p != property)
+                .nth(1)
+                .copied(),
+        };
+        self.refresh();
+    }
+
+    pub fn cycle_order(&mut self) {
+        self.order = self.order.next();
+        self.refresh();
+    }
+
+    /// Rebuilds the shown entries from the filter and order, keeping the
+    /// highlighted entry selected if it is still shown.
+    fn refresh(&mut self) {
+        let selected = self.selected().map(|(name, _)| name);
+        let mut items: Vec<(&'static str, View)> = self
+            .all
+            .iter()
+            .filter(|(_, view)| match self.filter {
+                Some(property) => view.info().is_some_and(|info| property.holds(info)),
+                None => true,
+            })
+            .copied()
+            .collect();
+        // Entries without complexities, such as the complexity view, go last
+        let rank = |view: View, pick: fn(&sort::Info) -> sort::Complexity| {
+            view.info().map_or(sort::Complexity::Unbounded, pick)
+        };
+        match self.order {
+            MenuOrder::Default => (),
+            MenuOrder::Name => items.sort_by_key(|(name, _)| *name),
+            MenuOrder::Best => items.sort_by_key(|(_, view)| rank(*view, |info| info.best)),
+            MenuOrder::Average => items.sort_by_key(|(_, view)| rank(*view, |info| info.average)),
+            MenuOrder::Worst => items.sort_by_key(|(_, view)| rank(*view, |info| info.worst)),
+            MenuOrder::Space => items.sort_by_key(|(_, view)| rank(*view, |info| info.space)),
+        }
+        let index = items
+            .iter()
+            .position(|(name, _)| Some(*name) == selected)
+            .unwrap_or(0);
+        self.list.state.select((!items.is_empty()).then_some(index));
+        self.list.items = items;
+    }
+}
+
+/// The fields of the settings view, in the order they are listed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    Size,
+    Distribution,
+    Speed,
+    Seed,
+    RenderStyle,
+    Theme,
+}
+
+impl SettingsField {
+    pub const ALL: [SettingsField; 6] = [
+        SettingsField::Size,
+        SettingsField::Distribution,
+        SettingsField::Speed,
+        SettingsField::Seed,
+        SettingsField::RenderStyle,
+        SettingsField::Theme,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            SettingsField::Size => "Array size",
+            SettingsField::Distribution => "Distribution",
+            SettingsField::Speed => "Speed",
+            SettingsField::Seed => "Seed",
+            SettingsField::RenderStyle => "Render style",
+            SettingsField::Theme => "Theme",
+        }
+    }
+}
+
+/// The settings being edited, and the view to return to afterwards.
+pub struct SettingsState {
+    pub list: StatefulList<SettingsField>,
+    pub back: View,
+}
+
+impl SettingsState {
+    pub fn new(back: View) -> SettingsState {
+        SettingsState {
+            list: StatefulList::default()
+                .set_items(SettingsField::ALL.to_vec())
+                .initial_select(0),
+            back,
+        }
+    }
+}
+
+/// The item the edit cursor is on, and the view to return to afterwards.
+pub struct EditState {
+    pub cursor: usize,
+    /// The distribution the items were last filled from, which starts at
+    /// the configured one but never changes it.
+    pub preset: Distribution,
+    pub back: View,
 }
 
 pub struct SortState<T>
@@ -93,6 +380,81 @@ where
 
 pub struct AppStates {
     pub menu: Option<MenuState>,
+    pub complexity: Option<ComplexityState>,
+    pub settings: Option<SettingsState>,
+    pub edit: Option<EditState>,
+}
+
+impl Default for AppStates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The algorithms to measure in the complexity view, which of them are
+/// chosen, and the measurements of the last run.
+pub struct ComplexityState {
+    pub list: StatefulList<&'static Algorithm>,
+    pub chosen: Vec<bool>,
+    pub max_size: usize,
+    pub results: Vec<complexity::Series>,
+    /// The run still in progress, whose series join `results` as they finish.
+    pub measurement: Option<complexity::Measurement>,
+}
+
+impl ComplexityState {
+    pub fn new() -> ComplexityState {
+        // Stooge sort and bogosort are far too slow to run over many sizes
+        let items: Vec<&'static Algorithm> = registry::ALGORITHMS
+            .iter()
+            .filter(|algorithm| !matches!(algorithm.id, "stooge" | "bogo"))
+            .collect();
+        let chosen = items
+            .iter()
+            .map(|algorithm| matches!(algorithm.id, "bubble" | "merge" | "heap"))
+            .collect();
+        ComplexityState {
+            list: StatefulList::default().set_items(items).initial_select(0),
+            chosen,
+            max_size: 128,
+            results: vec![],
+            measurement: None,
+        }
+    }
+
+    /// Measures the chosen algorithms in the background, replacing the
+    /// results of the last run.
+    pub fn run(&mut self) {
+        let algorithms = self
+            .list
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.chosen[*i])
+            .map(|(i, algorithm)| (i, *algorithm))
+            .collect();
+        self.results.clear();
+        self.measurement = Some(complexity::Measurement::start(
+            algorithms,
+            complexity::sizes(self.max_size),
+        ));
+    }
+
+    /// Collects the series measured since the last call.
+    pub fn poll(&mut self) {
+        if let Some(measurement) = self.measurement.as_mut() {
+            self.results.extend(measurement.poll());
+            if measurement.is_done() {
+                self.measurement = None;
+            }
+        }
+    }
+}
+
+impl Default for ComplexityState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AppStates {
@@ -101,19 +463,51 @@ impl AppStates {
 
         AppStates {
             menu: Some(menu_state),
+            complexity: None,
+            settings: None,
+            edit: None,
         }
     }
 }
 
+impl Default for App {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
 impl App {
-    pub fn new() -> App {
+    pub fn new(config: Config) -> App {
         let states = AppStates::new();
+        let themes = Theme::PRESETS
+            .iter()
+            .map(|preset| {
+                if preset.name == config.theme.name {
+                    config.theme
+                } else {
+                    *preset
+                }
+            })
+            .collect();
 
         App {
             current_view: View::Menu,
             ui_width: 0,
+            render_style: RenderStyle::Bars,
             states,
             sort: None,
+            metrics_history: MetricsHistory::default(),
+            size: config.size,
+            distribution: config.distribution,
+            seed: config.seed,
+            speed: config.speed,
+            theme: config.theme,
+            themes,
+            keymap: config.keymap,
+            show_help: false,
+            areas: MouseAreas::default(),
+            inspected: None,
+            seeking: false,
         }
     }
 
@@ -125,13 +519,495 @@ impl App {
         self.current_view = view;
     }
 
+    /// How many bars fit across the chart once the current sort's side
+    /// panel, if it has one, has taken its columns. Bars are two columns
+    /// apart when they fit at all.
+    pub fn chart_width(&self) -> usize {
+        let panel = self
+            .sort
+            .as_ref()
+            .map_or(0, |sort| ui::side_panel_width(sort.as_ref()));
+        self.ui_width.saturating_sub(panel.div_ceil(2)) as usize
+    }
+
+    /// Fresh items in the configured distribution, no more than fit on screen.
+    pub fn generate_items(&self) -> Vec<f64> {
+        let width = self.chart_width();
+        let size = self.size.map_or(width, |size| size.min(width));
+        self.generate(self.distribution, size)
+    }
+
+    /// Refills the current sort if the items it was started on do not fit
+    /// beside its side panel, or would not fill the chart.
+    fn fit_items(&mut self) {
+        let items = self.generate_items();
+        if let Some(sort) = self
+            .sort
+            .as_mut()
+            .filter(|sort| sort.items().len() != items.len())
+        {
+            sort.reset(items);
+        }
+    }
+
+    /// Fresh items in `distribution`, `size` of them, from the configured
+    /// seed if there is one.
+    pub fn generate(&self, distribution: Distribution, size: usize) -> Vec<f64> {
+        match self.seed {
+            Some(seed) => data::generate_seeded(distribution, size, seed),
+            None => data::generate(distribution, size),
+        }
+    }
+
+    /// The current value of a settings field, as shown in the settings view.
+    pub fn setting_value(&self, field: SettingsField) -> String {
+        match field {
+            SettingsField::Size => match self.size {
+                Some(size) => size.to_string(),
+                None => format!("{} (fit to screen)", self.chart_width()),
+            },
+            SettingsField::Distribution => self.distribution.get_name().to_string(),
+            SettingsField::Speed => format!("{} ms per step", self.speed.as_millis()),
+            SettingsField::Seed => self
+                .seed
+                .map_or("random".to_string(), |seed| seed.to_string()),
+            SettingsField::RenderStyle => self.render_style.get_name().to_string(),
+            SettingsField::Theme => self.theme.name.to_string(),
+        }
+    }
+
+    /// Steps a settings field to its next value, or its previous one when not
+    /// `forward`. Changes to the data restart the current sort on new items.
+    pub fn change_setting(&mut self, field: SettingsField, forward: bool) {
+        match field {
+            SettingsField::Size => {
+                // Past the screen width the size goes back to fitting it
+                let width = self.chart_width();
+                let size = self.size.unwrap_or(width);
+                let step = if size > 10 { 5 } else { 1 };
+                self.size = match forward {
+                    true if size >= width => None,
+                    true => Some((size + step).min(width)),
+                    false => Some(size.saturating_sub(step).max(1)),
+                };
+            }
+            SettingsField::Distribution => {
+                self.distribution = cycle(&Distribution::ALL, self.distribution, forward)
+            }
+            SettingsField::Speed => {
+                let millis = self.speed.as_millis() as u64;
+                let millis = if forward { millis * 2 } else { millis / 2 };
+                self.speed = Duration::from_millis(millis.clamp(1, 1024));
+            }
+            SettingsField::Seed => {
+                // Past either end the seed goes back to random
+                self.seed = match (self.seed, forward) {
+                    (None, true) => Some(0),
+                    (None, false) => None,
+                    (Some(seed), true) => seed.checked_add(1),
+                    (Some(seed), false) => seed.checked_sub(1),
+                }
+            }
+            SettingsField::RenderStyle => {
+                // Only sorting networks have a wire diagram to show. From the
+                // menu the next sort is not known yet, and opening it falls
+                // back to bars when it has no network.
+                let view = match self.current_view {
+                    View::Settings => self
+                        .states
+                        .settings
+                        .as_ref()
+                        .map_or(View::Menu, |settings| settings.back),
+                    view => view,
+                };
+                let has_network = match view {
+                    View::Sort(_) | View::Script(_) | View::Edit => self
+                        .sort
+                        .as_ref()
+                        .is_some_and(|sort| sort.network().is_some()),
+                    _ => true,
+                };
+                self.render_style = cycle(&RenderStyle::ALL, self.render_style, forward);
+                if self.render_style == RenderStyle::WireDiagram && !has_network {
+                    self.render_style = cycle(&RenderStyle::ALL, self.render_style, forward);
+                }
+            }
+            SettingsField::Theme => {
+                let names: Vec<&str> = self.themes.iter().map(|theme| theme.name).collect();
+                let name = cycle(&names, self.theme.name, forward);
+                if let Some(theme) = self.themes.iter().find(|theme| theme.name == name) {
+                    self.theme = *theme;
+                }
+            }
+        }
+        if matches!(
+            field,
+            SettingsField::Size | SettingsField::Distribution | SettingsField::Seed
+        ) {
+            let items = self.generate_items();
+            if let Some(sort) = self.sort.as_mut() {
+                sort.reset(items);
+            }
+        }
+    }
+
+    /// Leaves the current view: the settings and the editor return to the
+    /// view they were opened from, and everything else to the menu.
+    pub fn back(&mut self) {
+        let view = match self.current_view {
+            View::Settings => self
+                .states
+                .settings
+                .as_ref()
+                .map_or(View::Menu, |settings| settings.back),
+            View::Edit => self
+                .states
+                .edit
+                .as_ref()
+                .map_or(View::Menu, |edit| edit.back),
+            _ => View::Menu,
+        };
+        self.set_current_view(view);
+    }
+
+    /// Switches to `view`, starting its sort on fresh data.
+    pub fn open(&mut self, view: View) {
+        let items = self.generate_items();
+        match view {
+            View::Settings => {
+                self.states.settings = Some(SettingsState::new(self.current_view));
+            }
+            // The sort is put back to its start, on the items it shows now
+            View::Edit => {
+                if let Some(sort) = self.sort.as_mut() {
+                    let items = sort.items().clone();
+                    sort.reset(items);
+                }
+                self.states.edit = Some(EditState {
+                    cursor: 0,
+                    preset: self.distribution,
+                    back: self.current_view,
+                });
+            }
+            View::Sort(algorithm) => {
+                self.sort = Some(algorithm.create(items));
+                self.fit_items();
+            }
+            View::Script(script) => {
+                self.sort = Some(Box::new(ScriptSort::new(items, script)));
+                self.fit_items();
+            }
+            View::Complexity => {
+                if self.states.complexity.is_none() {
+                    self.states.complexity = Some(ComplexityState::new());
+                }
+            }
+            View::Menu => (),
+        }
+        // A wire diagram picked for a sorting network has nothing to show
+        // for the next sort, unless that is a network too
+        if let (View::Sort(_) | View::Script(_), Some(sort)) = (view, self.sort.as_ref()) {
+            if self.render_style == RenderStyle::WireDiagram && sort.network().is_none() {
+                self.render_style = RenderStyle::Bars;
+            }
+        }
+        self.inspected = None;
+        self.set_current_view(view);
+    }
+
+    /// Advances whatever runs in the background: the current sort, if it is
+    /// playing, and any complexity measurement.
+    pub fn on_tick(&mut self) {
+        if let Some(sort) = self.sort.as_mut() {
+            if sort.is_active() {
+                sort.step();
+            }
+        }
+        if let Some(state) = self.states.complexity.as_mut() {
+            state.poll();
+        }
+    }
+
     pub fn handle_input(&mut self, key: event::KeyEvent) -> Result<(), io::Error> {
+        // Any key closes the help overlay
+        if self.show_help {
+            self.show_help = false;
+            return Ok(());
+        }
+        if self.keymap.action(key.code, self.current_view.actions()) == Some(Action::Help) {
+            self.show_help = true;
+            return Ok(());
+        }
         match self.current_view {
             View::Menu => {
                 handlers::handle_menu_input(key, self)?;
             }
-            _ => handlers::handle_sort_input(key, self)?,
+            View::Sort(_) | View::Script(_) => handlers::handle_sort_input(key, self)?,
+            View::Complexity => handlers::handle_complexity_input(key, self)?,
+            View::Settings => handlers::handle_settings_input(key, self)?,
+            View::Edit => handlers::handle_edit_input(key, self)?,
+        }
+        Ok(())
+    }
+
+    pub fn handle_mouse(&mut self, mouse: event::MouseEvent) -> Result<(), io::Error> {
+        // Any click closes the help overlay
+        if self.show_help {
+            if let event::MouseEventKind::Down(_) = mouse.kind {
+                self.show_help = false;
+            }
+            return Ok(());
+        }
+        match self.current_view {
+            View::Menu => handlers::handle_menu_mouse(mouse, self)?,
+            View::Sort(_) | View::Script(_) => handlers::handle_sort_mouse(mouse, self)?,
+            View::Edit => handlers::handle_edit_mouse(mouse, self)?,
+            _ => (),
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use tui::style::Color;
+
+    #[test]
+    fn test_cycle() {
+        let all = [1, 2, 3];
+        assert_eq!(cycle(&all, 3, true), 1);
+        assert_eq!(cycle(&all, 1, false), 3);
+        assert_eq!(cycle(&all, 2, true), 3);
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut app = App {
+            ui_width: 40,
+            ..App::default()
+        };
+        app.open(View::Sort(registry::find("bubble").unwrap()));
+        app.open(View::Settings);
+
+        app.change_setting(SettingsField::Size, false);
+        assert_eq!(app.size, Some(35));
+        assert_eq!(app.sort.as_ref().unwrap().items().len(), 35);
+        app.change_setting(SettingsField::Size, true);
+        app.change_setting(SettingsField::Size, true);
+        assert_eq!(app.size, None);
+
+        // A seed makes every regenerated input the same
+        app.change_setting(SettingsField::Seed, true);
+        let items = app.sort.as_ref().unwrap().items().clone();
+        app.change_setting(SettingsField::Distribution, true);
+        app.change_setting(SettingsField::Distribution, false);
+        assert_eq!(*app.sort.as_ref().unwrap().items(), items);
+
+        app.seed = Some(u64::MAX - 1);
+        app.change_setting(SettingsField::Seed, true);
+        assert_eq!(app.seed, Some(u64::MAX));
+        app.change_setting(SettingsField::Seed, true);
+        assert_eq!(app.seed, None);
+        app.seed = Some(0);
+        app.change_setting(SettingsField::Seed, false);
+        assert_eq!(app.seed, None);
+
+        app.back();
+        assert!(matches!(app.current_view, View::Sort(_)));
+        app.back();
+        assert!(matches!(app.current_view, View::Menu));
+    }
+
+    #[test]
+    fn test_chart_width() {
+        let mut app = App {
+            ui_width: 40,
+            ..App::default()
+        };
+        let len = |app: &App| app.sort.as_ref().unwrap().items().len();
+        app.open(View::Sort(registry::find("bubble").unwrap()));
+        assert_eq!(len(&app), 40);
+
+        // The call stack takes twelve bars' worth of the chart
+        app.back();
+        app.open(View::Sort(registry::find("quick").unwrap()));
+        assert_eq!(len(&app), 28);
+        app.sort.as_mut().unwrap().reset(vec![1.0; 40]);
+        app.open(View::Settings);
+        app.change_setting(SettingsField::Seed, true);
+        assert_eq!(len(&app), 28);
+
+        // The variants take thirteen
+        app.back();
+        app.back();
+        app.open(View::Sort(registry::find("shell-ciura").unwrap()));
+        assert_eq!(len(&app), 27);
+
+        app.back();
+        app.open(View::Sort(registry::find("bubble").unwrap()));
+        assert_eq!(len(&app), 40);
+    }
+
+    #[test]
+    fn test_render_style() {
+        let mut app = App {
+            ui_width: 16,
+            ..App::default()
+        };
+        app.open(View::Sort(registry::find("bitonic").unwrap()));
+        app.render_style = RenderStyle::DisparityCircle;
+        app.change_setting(SettingsField::RenderStyle, true);
+        assert!(app.render_style == RenderStyle::WireDiagram);
+
+        // Bubble sort has no network, so it opens on bars
+        app.back();
+        app.open(View::Sort(registry::find("bubble").unwrap()));
+        assert!(app.render_style == RenderStyle::Bars);
+        app.render_style = RenderStyle::DisparityCircle;
+        app.change_setting(SettingsField::RenderStyle, true);
+        assert!(app.render_style == RenderStyle::Bars);
+
+        // From the menu, the settings cannot tell which sort comes next
+        app.back();
+        app.open(View::Settings);
+        app.render_style = RenderStyle::DisparityCircle;
+        app.change_setting(SettingsField::RenderStyle, true);
+        assert!(app.render_style == RenderStyle::WireDiagram);
+    }
+
+    #[test]
+    fn test_theme() {
+        let config = Config::parse("[theme]\npreset = \"light\"\npivot = \"red\"", false).unwrap();
+        let mut app = App::new(config);
+        app.open(View::Settings);
+        for _ in Theme::PRESETS {
+            app.change_setting(SettingsField::Theme, true);
+        }
+        assert_eq!(app.theme.name, "light");
+        assert_eq!(app.theme.pivot, Color::Red);
+
+        app.change_setting(SettingsField::Theme, false);
+        assert_eq!(app.theme, crate::theme::DARK);
+        app.change_setting(SettingsField::Theme, true);
+        assert_eq!(app.theme.pivot, Color::Red);
+    }
+
+    #[test]
+    fn test_help() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let mut app = App::default();
+        app.handle_input(key(KeyCode::Char('?'))).unwrap();
+        assert!(app.show_help);
+        // The key that closes the overlay does nothing else
+        app.handle_input(key(KeyCode::Char('c'))).unwrap();
+        assert!(!app.show_help);
+        assert!(matches!(app.current_view, View::Menu));
+        for view in [View::Menu, View::Complexity, View::Settings] {
+            assert!(view.actions().contains(&Action::Help));
+        }
+    }
+
+    #[test]
+    fn test_edit() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let key = |app: &mut App, code| {
+            app.handle_input(KeyEvent::new(code, KeyModifiers::NONE))
+                .unwrap()
+        };
+        let items = |app: &App| app.sort.as_ref().unwrap().items().clone();
+        let mut app = App {
+            ui_width: 4,
+            ..App::default()
+        };
+        app.open(View::Sort(registry::find("bubble").unwrap()));
+        app.sort.as_mut().unwrap().reset(vec![10.0, 20.0, 99.0]);
+        key(&mut app, KeyCode::Char('e'));
+        assert!(matches!(app.current_view, View::Edit));
+
+        key(&mut app, KeyCode::Up);
+        key(&mut app, KeyCode::Char('x'));
+        assert_eq!(items(&app), vec![20.0, 11.0, 99.0]);
+        key(&mut app, KeyCode::Right);
+        key(&mut app, KeyCode::Up);
+        key(&mut app, KeyCode::Char('i'));
+        key(&mut app, KeyCode::Char('i'));
+        assert_eq!(items(&app), vec![20.0, 11.0, 99.0, 99.0]);
+
+        // At least one item is always left to sort
+        for _ in 0..5 {
+            key(&mut app, KeyCode::Char('d'));
+        }
+        assert_eq!(items(&app), vec![20.0]);
+        assert_eq!(app.states.edit.as_ref().unwrap().cursor, 0);
+
+        // Presets refill the items without changing the configured distribution
+        key(&mut app, KeyCode::Char('p'));
+        assert_eq!(
+            app.states.edit.as_ref().unwrap().preset,
+            Distribution::Sorted
+        );
+        assert_eq!(app.distribution, Distribution::Random);
+        assert_eq!(items(&app).len(), 1);
+
+        // Starting runs the sort on the edited items
+        key(&mut app, KeyCode::Enter);
+        assert!(matches!(app.current_view, View::Sort(_)));
+        let sort = app.sort.as_ref().unwrap();
+        assert!(sort.is_active());
+        assert_eq!(sort.stepper().input, items(&app));
+    }
+
+    #[test]
+    fn test_mouse() {
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+        let mut app = App {
+            ui_width: 20,
+            ..App::default()
+        };
+        let click = |app: &mut App, kind, column, row| {
+            let mouse = MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            };
+            app.handle_mouse(mouse).unwrap();
+        };
+        let left = MouseEventKind::Down(MouseButton::Left);
+
+        // The first click selects an entry, the second opens it
+        app.areas.menu = Rect::new(0, 5, 20, 10);
+        app.states.menu.as_mut().unwrap().offset = 1;
+        click(&mut app, left, 3, 6);
+        let menu = app.states.menu.as_ref().unwrap();
+        assert_eq!(menu.list.state.selected(), Some(2));
+        click(&mut app, left, 3, 6);
+        assert!(matches!(app.current_view, View::Sort(_)));
+
+        // Bars sit on every other column, from the left of the chart
+        app.areas.bars = Some((Rect::new(10, 0, 20, 10), 1));
+        click(&mut app, left, 16, 4);
+        assert_eq!(app.inspected, Some(3));
+        click(&mut app, left, 17, 4);
+        assert_eq!(app.inspected, None);
+
+        app.areas.timeline = Rect::new(0, 20, 11, 1);
+        let total = app.sort.as_mut().unwrap().total_steps();
+        click(&mut app, left, 10, 20);
+        assert!(app.sort.as_ref().unwrap().is_sorted());
+        click(&mut app, MouseEventKind::Drag(MouseButton::Left), 0, 25);
+        assert_eq!(app.sort.as_ref().unwrap().get_current_step(), 0);
+        click(&mut app, MouseEventKind::Up(MouseButton::Left), 0, 25);
+        click(&mut app, MouseEventKind::Drag(MouseButton::Left), 5, 25);
+        assert_eq!(app.sort.as_ref().unwrap().get_current_step(), 0);
+        click(&mut app, left, 5, 20);
+        let step = app.sort.as_ref().unwrap().get_current_step();
+        assert_eq!(step, (total as f64 / 2.0).round() as usize);
+
+        let speed = app.speed;
+        click(&mut app, MouseEventKind::ScrollUp, 0, 0);
+        assert_eq!(app.speed, speed / 2);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::registry::Algorithm;
+use crate::sort::{generate_random_data, Counters};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+pub const MIN_SIZE: usize = 8;
+pub const MAX_SIZE: usize = 256;
+
+/// Number of input sizes measured between `MIN_SIZE` and the largest size.
+const POINTS: usize = 16;
+
+/// The operation counts of one algorithm at every measured input size.
+pub struct Series {
+    /// Position of the algorithm in the complexity view's list.
+    pub index: usize,
+    pub name: &'static str,
+    pub points: Vec<(usize, Counters)>,
+}
+
+impl Series {
+    pub fn comparisons(&self) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|(n, counters)| (*n as f64, counters.comparisons as f64))
+            .collect()
+    }
+    pub fn swaps(&self) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|(n, counters)| (*n as f64, counters.swaps as f64))
+            .collect()
+    }
+}
+
+/// Evenly spaced input sizes from `MIN_SIZE` up to `max_size`.
+pub fn sizes(max_size: usize) -> Vec<usize> {
+    let step = (max_size / POINTS).max(1);
+    (1..=POINTS)
+        .map(|k| (k * step).max(MIN_SIZE))
+        .filter(|&n| n <= max_size)
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Sorts random data of every size to completion without drawing it, and
+/// records the operation counts it took.
+pub fn measure(index: usize, algorithm: &'static Algorithm, sizes: &[usize]) -> Series {
+    let points = sizes
+        .iter()
+        .map(|&n| {
+            let mut sort = algorithm.create(generate_random_data(n));
+            while !sort.is_sorted() {
+                sort.step();
+            }
+            (n, *sort.counters())
+        })
+        .collect();
+    Series {
+        index,
+        name: algorithm.name,
+        points,
+    }
+}
+
+/// A run of `measure` over several algorithms on a worker thread, so the
+/// interface stays responsive while the slower ones sort their largest inputs.
+/// Dropping it stops the worker once the algorithm it is on is measured.
+pub struct Measurement {
+    receiver: Receiver<Series>,
+    /// The algorithms still to be measured, in order.
+    pub pending: Vec<&'static str>,
+    pub total: usize,
+}
+
+impl Measurement {
+    pub fn start(algorithms: Vec<(usize, &'static Algorithm)>, sizes: Vec<usize>) -> Measurement {
+        let (sender, receiver) = mpsc::channel();
+        let pending = algorithms
+            .iter()
+            .map(|(_, algorithm)| algorithm.name)
+            .collect();
+        let total = algorithms.len();
+        thread::spawn(move || {
+            for (index, algorithm) in algorithms {
+                if sender.send(measure(index, algorithm, &sizes)).is_err() {
+                    break;
+                }
+            }
+        });
+        Measurement {
+            receiver,
+            pending,
+            total,
+        }
+    }
+
+    /// The series measured since the last call.
+    pub fn poll(&mut self) -> Vec<Series> {
+        let mut finished = vec![];
+        loop {
+            match self.receiver.try_recv() {
+                Ok(series) => {
+                    self.pending.retain(|name| *name != series.name);
+                    finished.push(series);
+                }
+                // A worker that panicked leaves the rest unmeasured
+                Err(TryRecvError::Disconnected) => {
+                    self.pending.clear();
+                    break;
+                }
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+        finished
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// Fits `y = c * n^k` by least squares on the logarithms, returning `k`.
+/// Points with no operations are left out, as their logarithm is undefined.
+pub fn fit_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(n, y)| *n > 0.0 && *y > 0.0)
+        .map(|(n, y)| (n.ln(), y.ln()))
+        .collect();
+    if logs.len() < 2 {
+        return None;
+    }
+    let count = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(sizes(128), (1..=16).map(|k| k * 8).collect::<Vec<_>>());
+        assert_eq!(
+            sizes(64),
+            vec![8, 12, 16, 20, 24, 28, 32, 36, 40, 44, 48, 52, 56, 60, 64]
+        );
+    }
+
+    #[test]
+    fn test_fit_exponent() {
+        let square: Vec<(f64, f64)> = (1..10).map(|n| (n as f64, 3.0 * (n * n) as f64)).collect();
+        assert!((fit_exponent(&square).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(fit_exponent(&[(8.0, 0.0), (16.0, 0.0)]), None);
+    }
+
+    #[test]
+    fn test_measure() {
+        let series = measure(0, registry::find("bubble").unwrap(), &[8, 16, 32]);
+        assert_eq!(series.points.len(), 3);
+        // Bubble sort always makes n(n - 1) / 2 comparisons
+        assert_eq!(series.points[2].1.comparisons, 32 * 31 / 2);
+    }
+
+    #[test]
+    fn test_measurement() {
+        let algorithms = vec![
+            (0, registry::find("bubble").unwrap()),
+            (3, registry::find("merge").unwrap()),
+        ];
+        let mut measurement = Measurement::start(algorithms, vec![8, 16]);
+        assert_eq!(measurement.total, 2);
+        let mut results = vec![];
+        while !measurement.is_done() {
+            results.extend(measurement.poll());
+            thread::yield_now();
+        }
+        let indices: Vec<usize> = results.iter().map(|series| series.index).collect();
+        assert_eq!(indices, vec![0, 3]);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::keymap::{self, Action, Keymap};
+use crate::theme::{self, Theme};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fs, io};
+use term_sort::data::Distribution;
+use term_sort::registry::{self, Algorithm};
+
+/// The settings read from `config.toml`, with defaults for anything the file
+/// leaves out.
+pub struct Config {
+    /// The algorithm opened when none is given on the command line.
+    pub algorithm: Option<&'static Algorithm>,
+    /// How many items to sort, at most one per column of the chart.
+    pub size: Option<usize>,
+    /// How long each step stays on screen.
+    pub speed: Duration,
+    pub distribution: Distribution,
+    /// Generates the same items every run when set.
+    pub seed: Option<u64>,
+    pub theme: Theme,
+    pub keymap: Keymap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            algorithm: None,
+            size: None,
+            speed: Duration::from_millis(12),
+            distribution: Distribution::Random,
+            seed: None,
+            theme: Theme::default(),
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RawConfig {
+    defaults: RawDefaults,
+    theme: BTreeMap<String, String>,
+    keys: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RawDefaults {
+    algorithm: Option<String>,
+    size: Option<usize>,
+    speed: Option<u64>,
+    distribution: Option<String>,
+    seed: Option<u64>,
+}
+
+/// `$XDG_CONFIG_HOME/term-sort/config.toml`, or under `~/.config` when that
+/// is not set.
+pub fn path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("term-sort").join("config.toml"))
+}
+
+/// The name a distribution goes by in the config file, e.g. `nearly-sorted`.
+fn distribution_name(distribution: Distribution) -> String {
+    distribution.get_name().to_lowercase().replace(' ', "-")
+}
+
+impl Config {
+    /// Reads the config file, if there is one. Setting `NO_COLOR` starts on
+    /// the monochrome theme unless the file picks another.
+    pub fn load() -> Result<Config, io::Error> {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let path = match path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Config::parse("", no_color).unwrap()),
+        };
+        let invalid = |message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid config {}: {}", path.display(), message),
+            )
+        };
+        let text = fs::read_to_string(&path).map_err(|error| invalid(error.to_string()))?;
+        Config::parse(&text, no_color).map_err(invalid)
+    }
+
+    pub fn parse(text: &str, no_color: bool) -> Result<Config, String> {
+        let mut raw: RawConfig = toml::from_str(text).map_err(|error| match error.span() {
+            Some(span) => {
+                let line = text[..span.start].matches('\n').count() + 1;
+                format!("line {}: {}", line, error.message())
+            }
+            None => error.message().to_string(),
+        })?;
+        let mut config = Config::default();
+
+        let defaults = raw.defaults;
+        if let Some(id) = defaults.algorithm {
+            config.algorithm = Some(registry::find(&id).ok_or_else(|| {
+                format!(
+                    "unknown algorithm `{}` in [defaults], see --list for the ids",
+                    id
+                )
+            })?);
+        }
+        match defaults.size {
+            Some(0) => return Err("size in [defaults] must be at least 1".to_string()),
+            size => config.size = size,
+        }
+        config.seed = defaults.seed;
+        match defaults.speed {
+            Some(0) => return Err("speed in [defaults] must be at least 1ms".to_string()),
+            Some(speed) => config.speed = Duration::from_millis(speed),
+            None => (),
+        }
+        if let Some(name) = defaults.distribution {
+            config.distribution = Distribution::ALL
+                .into_iter()
+                .find(|distribution| distribution_name(*distribution) == name)
+                .ok_or_else(|| {
+                    let names: Vec<String> = Distribution::ALL
+                        .into_iter()
+                        .map(distribution_name)
+                        .collect();
+                    format!(
+                        "unknown distribution `{}` in [defaults], expected one of {}",
+                        name,
+                        names.join(", ")
+                    )
+                })?;
+        }
+
+        // Colours set alongside a preset are applied on top of it
+        match raw.theme.remove("preset") {
+            Some(name) => {
+                config.theme = Theme::find(&name).ok_or_else(|| {
+                    let names: Vec<&str> = Theme::PRESETS.iter().map(|theme| theme.name).collect();
+                    format!(
+                        "unknown preset `{}` in [theme], expected one of {}",
+                        name,
+                        names.join(", ")
+                    )
+                })?
+            }
+            None if no_color => config.theme = theme::MONOCHROME,
+            None => (),
+        }
+        for (element, value) in raw.theme {
+            let color = config.theme.element_mut(&element).ok_or_else(|| {
+                format!(
+                    "unknown element `{}` in [theme], expected preset or one of {}",
+                    element,
+                    Theme::ELEMENTS.join(", ")
+                )
+            })?;
+            *color = theme::parse_color(&value).ok_or_else(|| {
+                format!(
+                    "invalid colour `{}` for {} in [theme], expected a name like `light_blue` or `#rrggbb`",
+                    value, element
+                )
+            })?;
+        }
+
+        for (name, value) in raw.keys {
+            let action = Action::ALL
+                .into_iter()
+                .find(|action| action.get_name() == name)
+                .ok_or_else(|| {
+                    let names: Vec<&str> = Action::ALL.iter().map(Action::get_name).collect();
+                    format!(
+                        "unknown action `{}` in [keys], expected one of {}",
+                        name,
+                        names.join(", ")
+                    )
+                })?;
+            let key = keymap::parse_key(&value)
+                .ok_or_else(|| format!("invalid key `{}` for {} in [keys]", value, name))?;
+            config.keymap.bind(action, key);
+        }
+        if let Some((a, b)) = config.keymap.conflict() {
+            return Err(format!(
+                "{} and {} are both bound to `{}` in [keys]",
+                a.get_name(),
+                b.get_name(),
+                keymap::key_name(config.keymap.key(a))
+            ));
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use tui::style::Color;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r##"
+            [defaults]
+            algorithm = "quick"
+            size = 30
+            speed = 40
+            distribution = "nearly-sorted"
+            seed = 42
+
+            [theme]
+            sorting = "light_blue"
+            footer = "#102030"
+
+            [keys]
+            start = "space"
+            "##,
+            false,
+        )
+        .unwrap();
+        assert_eq!(config.algorithm.unwrap().id, "quick");
+        assert_eq!(config.size, Some(30));
+        assert_eq!(config.speed, Duration::from_millis(40));
+        assert_eq!(config.distribution, Distribution::NearlySorted);
+        assert_eq!(config.seed, Some(42));
+        assert_eq!(config.theme.sorting, Color::LightBlue);
+        assert_eq!(config.theme.footer, Color::Rgb(0x10, 0x20, 0x30));
+        assert_eq!(config.theme.complete, Theme::default().complete);
+        assert_eq!(config.keymap.key(Action::Start), KeyCode::Char(' '));
+    }
+
+    #[test]
+    fn test_empty() {
+        let config = Config::parse("", false).unwrap();
+        assert!(config.algorithm.is_none());
+        assert_eq!(config.theme, Theme::default());
+    }
+
+    #[test]
+    fn test_preset() {
+        let config = Config::parse("[theme]\npreset = \"light\"\npivot = \"red\"", false).unwrap();
+        assert_eq!(config.theme.name, "light");
+        assert_eq!(config.theme.pivot, Color::Red);
+        assert_eq!(config.theme.text, theme::LIGHT.text);
+        // NO_COLOR only changes the theme the file leaves unset
+        assert_eq!(Config::parse("", true).unwrap().theme, theme::MONOCHROME);
+        let config = Config::parse("[theme]\npreset = \"dark\"", true).unwrap();
+        assert_eq!(config.theme, theme::DARK);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |text: &str| Config::parse(text, false).err().unwrap();
+        assert!(error("[defaults]\nalgorithm = \"nope\"").contains("unknown algorithm `nope`"));
+        assert!(error("[defaults]\nsize = 0").contains("size"));
+        assert!(error("[defaults]\nspeed = -1").contains("invalid value"));
+        assert!(error("[defaults]\ndistribution = \"wavy\"").contains("few-unique"));
+        assert!(error("[defaults]\ncolour = 1").starts_with("line 2: unknown field `colour`"));
+        assert!(error("[theme]\nborder = \"red\"").contains("unknown element `border`"));
+        assert!(error("[theme]\npivot = \"mauve\"").contains("invalid colour `mauve` for pivot"));
+        assert!(error("[theme]\npreset = \"neon\"").contains("monochrome"));
+        assert!(error("[keys]\njump = \"j\"").contains("unknown action `jump`"));
+        assert!(error("[keys]\nquit = \"ctrl-q\"").contains("invalid key `ctrl-q`"));
+        assert!(error("[keys]\nrender = \"o\"").contains("render and sorted_input"));
+        // Sort settings share the sort view's keys
+        assert!(error("[keys]\nsorted_input = \"s\"").contains("sorted_input and partition"));
+        assert!(error("[keys]\nrender = \"p\"").contains("render and pivot"));
+        assert!(Config::parse("[keys]\nrender = \"p\"\npivot = \"g\"", false).is_ok());
+    }
+}
@@ -0,0 +1,118 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// The shapes of input the sorts can be started on.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Distribution {
+    Random,
+    Sorted,
+    Reversed,
+    NearlySorted,
+    FewUnique,
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 5] = [
+        Distribution::Random,
+        Distribution::Sorted,
+        Distribution::Reversed,
+        Distribution::NearlySorted,
+        Distribution::FewUnique,
+    ];
+
+    pub fn next(&self) -> Distribution {
+        match self {
+            Distribution::Random => Distribution::Sorted,
+            Distribution::Sorted => Distribution::Reversed,
+            Distribution::Reversed => Distribution::NearlySorted,
+            Distribution::NearlySorted => Distribution::FewUnique,
+            Distribution::FewUnique => Distribution::Random,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Distribution::Random => "Random",
+            Distribution::Sorted => "Sorted",
+            Distribution::Reversed => "Reversed",
+            Distribution::NearlySorted => "Nearly Sorted",
+            Distribution::FewUnique => "Few Unique",
+        }
+    }
+}
+
+/// The largest value `generate` produces.
+pub const MAX_VALUE: f64 = 99.0;
+
+/// Generates `size` whole numbers in `0..100` shaped by `distribution`.
+pub fn generate(distribution: Distribution, size: usize) -> Vec<f64> {
+    generate_with(distribution, size, &mut rand::thread_rng())
+}
+
+/// Like `generate`, but always the same items for the same `seed`.
+pub fn generate_seeded(distribution: Distribution, size: usize, seed: u64) -> Vec<f64> {
+    generate_with(distribution, size, &mut StdRng::seed_from_u64(seed))
+}
+
+fn generate_with(distribution: Distribution, size: usize, rng: &mut impl Rng) -> Vec<f64> {
+    let mut items: Vec<f64> = match distribution {
+        Distribution::FewUnique => (0..size)
+            .map(|_| (rng.gen_range(0..4) * 25 + 12) as f64)
+            .collect(),
+        _ => (0..size)
+            .map(|_| (rng.gen::<f64>() * (MAX_VALUE + 1.0)).floor())
+            .collect(),
+    };
+    match distribution {
+        Distribution::Sorted => items.sort_by(f64::total_cmp),
+        Distribution::Reversed => items.sort_by(|a, b| b.total_cmp(a)),
+        // Sorted, then about a tenth of the elements swapped out of place
+        Distribution::NearlySorted => {
+            items.sort_by(f64::total_cmp);
+            for _ in 0..size / 20 {
+                items.swap(rng.gen_range(0..size), rng.gen_range(0..size));
+            }
+        }
+        Distribution::Random | Distribution::FewUnique => (),
+    }
+    items
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::test_util;
+
+    #[test]
+    fn test_shapes() {
+        for distribution in Distribution::ALL {
+            assert_eq!(generate(distribution, 50).len(), 50);
+        }
+        assert!(test_util::is_sorted(&generate(Distribution::Sorted, 50)));
+        let mut reversed = generate(Distribution::Reversed, 50);
+        reversed.reverse();
+        assert!(test_util::is_sorted(&reversed));
+    }
+
+    #[test]
+    fn test_few_unique() {
+        let mut items = generate(Distribution::FewUnique, 100);
+        items.sort_by(f64::total_cmp);
+        items.dedup();
+        assert!(items.len() <= 4);
+    }
+
+    #[test]
+    fn test_seeded() {
+        for distribution in Distribution::ALL {
+            assert_eq!(
+                generate_seeded(distribution, 50, 7),
+                generate_seeded(distribution, 50, 7)
+            );
+        }
+        assert_ne!(
+            generate_seeded(Distribution::Random, 50, 7),
+            generate_seeded(Distribution::Random, 50, 8)
+        );
+    }
+}
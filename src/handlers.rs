@@ -1,32 +1,29 @@
-use crate::app::{App, View};
-use crate::sort::{
-    bubble::BubbleSort, generate_random_data, insertion::InsertionSort, selection::SelectionSort,
-};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::app::{App, SettingsField, View};
+use crate::keymap::Action;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use std::io;
+use term_sort::complexity;
+use term_sort::data;
+use tui::layout::Rect;
 
 pub fn handle_menu_input(key: KeyEvent, app: &mut App) -> Result<(), io::Error> {
+    let action = app.keymap.action(key.code, app.current_view.actions());
     if let Some(menu) = app.states.menu.as_mut() {
-        match key.code {
-            KeyCode::Up => {
+        match action {
+            Some(Action::Up) => {
                 menu.list.previous();
             }
-            KeyCode::Down => {
+            Some(Action::Down) => {
                 menu.list.next();
             }
-            KeyCode::Enter => {
-                let selected = menu.list.state.selected().unwrap();
-                let items = generate_random_data(app.ui_width as usize);
-
-                if let Some((_, view)) = menu.list.items.get(selected) {
-                    let view = view.clone();
-                    app.sort = match view {
-                        View::Bubble => Some(Box::new(BubbleSort::new(items))),
-                        View::Insertion => Some(Box::new(InsertionSort::new(items))),
-                        View::Selection => Some(Box::new(SelectionSort::new(items))),
-                        _ => None,
-                    };
-                    app.set_current_view(view);
+            Some(Action::Info) => menu.show_info = !menu.show_info,
+            Some(Action::Filter) => menu.cycle_filter(),
+            Some(Action::Order) => menu.cycle_order(),
+            Some(Action::Theme) => app.change_setting(SettingsField::Theme, true),
+            Some(Action::Settings) => app.open(View::Settings),
+            Some(Action::Select) => {
+                if let Some((_, view)) = menu.selected() {
+                    app.open(view);
                 }
             }
             _ => (),
@@ -36,11 +33,11 @@ pub fn handle_menu_input(key: KeyEvent, app: &mut App) -> Result<(), io::Error>
 }
 
 pub fn handle_sort_input(key: KeyEvent, app: &mut App) -> Result<(), io::Error> {
-    match key.code {
-        KeyCode::Enter => {
+    match app.keymap.action(key.code, app.current_view.actions()) {
+        Some(Action::Start) => {
+            let items = app.generate_items();
             if let Some(sort) = app.sort.as_mut() {
                 if sort.is_sorted() {
-                    let items = generate_random_data(app.ui_width as usize);
                     sort.reset(items);
                     sort.activate_sort();
                 } else {
@@ -48,7 +45,221 @@ pub fn handle_sort_input(key: KeyEvent, app: &mut App) -> Result<(), io::Error>
                 }
             }
         }
+        Some(Action::Render) => app.change_setting(SettingsField::RenderStyle, true),
+        Some(Action::Theme) => app.change_setting(SettingsField::Theme, true),
+        Some(Action::Settings) => app.open(View::Settings),
+        Some(Action::Edit) => app.open(View::Edit),
+        Some(Action::SortedInput) => {
+            if let Some(sort) = app.sort.as_mut() {
+                let mut items = sort.items().clone();
+                items.sort_by(f64::total_cmp);
+                sort.reset(items);
+            }
+        }
+        Some(action) => {
+            if let (Some(name), Some(sort)) = (action.setting(), app.sort.as_mut()) {
+                sort.cycle_setting(name);
+            }
+        }
+        None => (),
+    }
+    Ok(())
+}
+
+/// Changes the items under the cursor, restarting the sort on them after
+/// every edit. The items are kept between one and a chart's width long.
+pub fn handle_edit_input(key: KeyEvent, app: &mut App) -> Result<(), io::Error> {
+    let action = app.keymap.action(key.code, app.current_view.actions());
+    let Some(mut items) = app
+        .sort
+        .as_ref()
+        .map(|sort| sort.items().clone())
+        .filter(|items| !items.is_empty())
+    else {
+        return Ok(());
+    };
+    let cursor = app.states.edit.as_ref().map_or(0, |edit| edit.cursor);
+    let cursor = cursor.min(items.len().saturating_sub(1));
+    let cursor = match action {
+        Some(Action::Start) => {
+            app.back();
+            if let Some(sort) = app.sort.as_mut() {
+                sort.activate_sort();
+            }
+            return Ok(());
+        }
+        Some(Action::Left) => cursor.saturating_sub(1),
+        Some(Action::Right) => (cursor + 1).min(items.len().saturating_sub(1)),
+        Some(Action::Raise) => {
+            items[cursor] = (items[cursor] + 1.0).min(data::MAX_VALUE);
+            cursor
+        }
+        Some(Action::Lower) => {
+            items[cursor] = (items[cursor] - 1.0).max(0.0);
+            cursor
+        }
+        // The cursor follows the item, so it can be carried along
+        Some(Action::Swap) if cursor + 1 < items.len() => {
+            items.swap(cursor, cursor + 1);
+            cursor + 1
+        }
+        Some(Action::Insert) if items.len() < app.chart_width() => {
+            items.insert(cursor + 1, items[cursor]);
+            cursor + 1
+        }
+        Some(Action::Delete) if items.len() > 1 => {
+            items.remove(cursor);
+            cursor.min(items.len() - 1)
+        }
+        Some(Action::Preset) => {
+            let Some(edit) = app.states.edit.as_mut() else {
+                return Ok(());
+            };
+            edit.preset = edit.preset.next();
+            let preset = edit.preset;
+            items = app.generate(preset, items.len());
+            cursor
+        }
+        _ => return Ok(()),
+    };
+    if let Some(edit) = app.states.edit.as_mut() {
+        edit.cursor = cursor;
+    }
+    if let Some(sort) = app.sort.as_mut().filter(|sort| *sort.items() != items) {
+        sort.reset(items);
+    }
+    Ok(())
+}
+
+/// Whether the cell at `column`, `row` falls inside `area`.
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    (area.x..area.right()).contains(&column) && (area.y..area.bottom()).contains(&row)
+}
+
+/// Clicking an entry selects it, and clicking it again opens it. The wheel
+/// moves the selection.
+pub fn handle_menu_mouse(mouse: MouseEvent, app: &mut App) -> Result<(), io::Error> {
+    let area = app.areas.menu;
+    if let Some(menu) = app.states.menu.as_mut() {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => menu.list.previous(),
+            MouseEventKind::ScrollDown => menu.list.next(),
+            MouseEventKind::Down(MouseButton::Left) if contains(area, mouse.column, mouse.row) => {
+                let index = menu.offset + (mouse.row - area.y) as usize;
+                if index < menu.list.items.len() {
+                    if menu.list.state.selected() == Some(index) {
+                        let (_, view) = menu.list.items[index];
+                        app.open(view);
+                    } else {
+                        menu.list.state.select(Some(index));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Clicking a bar inspects it, clicking or dragging along the timeline seeks,
+/// and the wheel speeds playback up or slows it down.
+pub fn handle_sort_mouse(mouse: MouseEvent, app: &mut App) -> Result<(), io::Error> {
+    let (column, row) = (mouse.column, mouse.row);
+    let timeline = app.areas.timeline;
+    match mouse.kind {
+        MouseEventKind::ScrollUp => app.change_setting(SettingsField::Speed, false),
+        MouseEventKind::ScrollDown => app.change_setting(SettingsField::Speed, true),
+        MouseEventKind::Down(MouseButton::Left) if contains(timeline, column, row) => {
+            app.seeking = true;
+            seek(app, column);
+        }
+        MouseEventKind::Drag(MouseButton::Left) if app.seeking => seek(app, column),
+        MouseEventKind::Up(MouseButton::Left) => app.seeking = false,
+        MouseEventKind::Down(MouseButton::Left) => app.inspected = bar_at(app, column, row),
         _ => (),
     }
     Ok(())
 }
+
+/// Clicking a bar moves the edit cursor onto it.
+pub fn handle_edit_mouse(mouse: MouseEvent, app: &mut App) -> Result<(), io::Error> {
+    if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+        if let (Some(index), Some(edit)) = (
+            bar_at(app, mouse.column, mouse.row),
+            app.states.edit.as_mut(),
+        ) {
+            edit.cursor = index;
+        }
+    }
+    Ok(())
+}
+
+/// The index of the bar drawn at `column`, `row`, if there is one there.
+fn bar_at(app: &App, column: u16, row: u16) -> Option<usize> {
+    let (inner, gap) = app.areas.bars?;
+    let count = app.sort.as_ref()?.items().len();
+    if !contains(inner, column, row) {
+        return None;
+    }
+    // Each bar is one column wide, followed by the gap
+    let offset = (column - inner.x) as usize;
+    let stride = 1 + gap as usize;
+    (offset.is_multiple_of(stride) && offset / stride < count).then_some(offset / stride)
+}
+
+/// Jumps to the step the timeline shows at `column`, from the start at its
+/// left end to the finish at its right.
+fn seek(app: &mut App, column: u16) {
+    let timeline = app.areas.timeline;
+    if let Some(sort) = app.sort.as_mut() {
+        let column = column.clamp(timeline.x, timeline.right().saturating_sub(1));
+        let last = timeline.width.saturating_sub(1).max(1) as f64;
+        let total = sort.total_steps() as f64;
+        let step = ((column - timeline.x) as f64 / last * total).round();
+        sort.seek(step as usize);
+    }
+}
+
+pub fn handle_complexity_input(key: KeyEvent, app: &mut App) -> Result<(), io::Error> {
+    let action = app.keymap.action(key.code, app.current_view.actions());
+    if let Some(state) = app.states.complexity.as_mut() {
+        match action {
+            Some(Action::Up) => state.list.previous(),
+            Some(Action::Down) => state.list.next(),
+            Some(Action::Choose) => {
+                if let Some(selected) = state.list.state.selected() {
+                    state.chosen[selected] = !state.chosen[selected];
+                }
+            }
+            Some(Action::Grow) => {
+                state.max_size = (state.max_size * 2).min(complexity::MAX_SIZE);
+            }
+            Some(Action::Shrink) => {
+                state.max_size = (state.max_size / 2).max(complexity::MIN_SIZE * 2);
+            }
+            Some(Action::Theme) => app.change_setting(SettingsField::Theme, true),
+            Some(Action::Run) => state.run(),
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_settings_input(key: KeyEvent, app: &mut App) -> Result<(), io::Error> {
+    let action = app.keymap.action(key.code, app.current_view.actions());
+    if let Some(settings) = app.states.settings.as_mut() {
+        let field = settings
+            .list
+            .state
+            .selected()
+            .map(|selected| settings.list.items[selected]);
+        match (action, field) {
+            (Some(Action::Up), _) => settings.list.previous(),
+            (Some(Action::Down), _) => settings.list.next(),
+            (Some(Action::Decrease), Some(field)) => app.change_setting(field, false),
+            (Some(Action::Increase), Some(field)) => app.change_setting(field, true),
+            _ => (),
+        }
+    }
+    Ok(())
+}
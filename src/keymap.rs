@@ -0,0 +1,369 @@
+use crossterm::event::KeyCode;
+
+/// Everything a key can be bound to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Quit,
+    Up,
+    Down,
+    Select,
+    Info,
+    Filter,
+    Order,
+    Start,
+    Render,
+    SortedInput,
+    Choose,
+    Grow,
+    Shrink,
+    Run,
+    Theme,
+    Settings,
+    Decrease,
+    Increase,
+    Help,
+    Edit,
+    Left,
+    Right,
+    Raise,
+    Lower,
+    Swap,
+    Insert,
+    Delete,
+    Preset,
+    Pivot,
+    Partition,
+}
+
+impl Action {
+    pub const ALL: [Action; 30] = [
+        Action::Quit,
+        Action::Up,
+        Action::Down,
+        Action::Select,
+        Action::Info,
+        Action::Filter,
+        Action::Order,
+        Action::Start,
+        Action::Render,
+        Action::SortedInput,
+        Action::Choose,
+        Action::Grow,
+        Action::Shrink,
+        Action::Run,
+        Action::Theme,
+        Action::Settings,
+        Action::Decrease,
+        Action::Increase,
+        Action::Help,
+        Action::Edit,
+        Action::Left,
+        Action::Right,
+        Action::Raise,
+        Action::Lower,
+        Action::Swap,
+        Action::Insert,
+        Action::Delete,
+        Action::Preset,
+        Action::Pivot,
+        Action::Partition,
+    ];
+
+    /// The sort setting the action cycles, which only some sorts have.
+    pub fn setting(&self) -> Option<&'static str> {
+        match self {
+            Action::Pivot => Some("pivot"),
+            Action::Partition => Some("partition"),
+            _ => None,
+        }
+    }
+
+    /// The name the action goes by in the config file.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Select => "select",
+            Action::Info => "info",
+            Action::Filter => "filter",
+            Action::Order => "order",
+            Action::Start => "start",
+            Action::Render => "render",
+            Action::SortedInput => "sorted_input",
+            Action::Choose => "choose",
+            Action::Grow => "grow",
+            Action::Shrink => "shrink",
+            Action::Run => "run",
+            Action::Theme => "theme",
+            Action::Settings => "settings",
+            Action::Decrease => "decrease",
+            Action::Increase => "increase",
+            Action::Help => "help",
+            Action::Edit => "edit",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Raise => "raise",
+            Action::Lower => "lower",
+            Action::Swap => "swap",
+            Action::Insert => "insert",
+            Action::Delete => "delete",
+            Action::Preset => "preset",
+            Action::Pivot => "pivot",
+            Action::Partition => "partition",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Action::Quit => "back, or quit from the menu",
+            Action::Up => "move up",
+            Action::Down => "move down",
+            Action::Select => "select sort",
+            Action::Info => "info",
+            Action::Filter => "filter",
+            Action::Order => "sort by",
+            Action::Start => "start/pause",
+            Action::Render => "change view",
+            Action::SortedInput => "sorted input",
+            Action::Choose => "choose",
+            Action::Grow => "larger inputs",
+            Action::Shrink => "smaller inputs",
+            Action::Run => "measure",
+            Action::Theme => "next theme",
+            Action::Settings => "settings",
+            Action::Decrease => "previous value",
+            Action::Increase => "next value",
+            Action::Help => "show these keys",
+            Action::Edit => "edit the items",
+            Action::Left => "previous item",
+            Action::Right => "next item",
+            Action::Raise => "raise value",
+            Action::Lower => "lower value",
+            Action::Swap => "swap with next item",
+            Action::Insert => "insert a copy",
+            Action::Delete => "delete item",
+            Action::Preset => "next distribution",
+            Action::Pivot => "next pivot strategy",
+            Action::Partition => "next partition scheme",
+        }
+    }
+}
+
+/// The actions each view responds to, in the order they are listed.
+pub const MENU: &[Action] = &[
+    Action::Quit,
+    Action::Up,
+    Action::Down,
+    Action::Select,
+    Action::Info,
+    Action::Filter,
+    Action::Order,
+    Action::Theme,
+    Action::Settings,
+    Action::Help,
+];
+pub const SORT: &[Action] = &[
+    Action::Quit,
+    Action::Start,
+    Action::Render,
+    Action::SortedInput,
+    Action::Edit,
+    Action::Theme,
+    Action::Settings,
+    Action::Help,
+    Action::Pivot,
+    Action::Partition,
+];
+pub const COMPLEXITY: &[Action] = &[
+    Action::Quit,
+    Action::Up,
+    Action::Down,
+    Action::Choose,
+    Action::Grow,
+    Action::Shrink,
+    Action::Run,
+    Action::Theme,
+    Action::Help,
+];
+pub const EDIT: &[Action] = &[
+    Action::Quit,
+    Action::Start,
+    Action::Left,
+    Action::Right,
+    Action::Raise,
+    Action::Lower,
+    Action::Swap,
+    Action::Insert,
+    Action::Delete,
+    Action::Preset,
+    Action::Help,
+];
+pub const SETTINGS: &[Action] = &[
+    Action::Quit,
+    Action::Up,
+    Action::Down,
+    Action::Decrease,
+    Action::Increase,
+    Action::Help,
+];
+
+/// The key bound to every action.
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: Vec<(Action, KeyCode)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap {
+            bindings: vec![
+                (Action::Quit, KeyCode::Char('q')),
+                (Action::Up, KeyCode::Up),
+                (Action::Down, KeyCode::Down),
+                (Action::Select, KeyCode::Enter),
+                (Action::Info, KeyCode::Char('i')),
+                (Action::Filter, KeyCode::Char('f')),
+                (Action::Order, KeyCode::Char('s')),
+                (Action::Start, KeyCode::Enter),
+                (Action::Render, KeyCode::Char('v')),
+                (Action::SortedInput, KeyCode::Char('o')),
+                (Action::Choose, KeyCode::Char(' ')),
+                (Action::Grow, KeyCode::Char('+')),
+                (Action::Shrink, KeyCode::Char('-')),
+                (Action::Run, KeyCode::Enter),
+                (Action::Theme, KeyCode::Char('t')),
+                (Action::Settings, KeyCode::Char('c')),
+                (Action::Decrease, KeyCode::Left),
+                (Action::Increase, KeyCode::Right),
+                (Action::Help, KeyCode::Char('?')),
+                (Action::Edit, KeyCode::Char('e')),
+                (Action::Left, KeyCode::Left),
+                (Action::Right, KeyCode::Right),
+                (Action::Raise, KeyCode::Up),
+                (Action::Lower, KeyCode::Down),
+                (Action::Swap, KeyCode::Char('x')),
+                (Action::Insert, KeyCode::Char('i')),
+                (Action::Delete, KeyCode::Char('d')),
+                (Action::Preset, KeyCode::Char('p')),
+                (Action::Pivot, KeyCode::Char('p')),
+                (Action::Partition, KeyCode::Char('s')),
+            ],
+        }
+    }
+}
+
+impl Keymap {
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map(|(_, key)| *key)
+            .unwrap()
+    }
+
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        for binding in self.bindings.iter_mut() {
+            if binding.0 == action {
+                binding.1 = key;
+            }
+        }
+    }
+
+    /// The action among `actions` that `key` is bound to.
+    pub fn action(&self, key: KeyCode, actions: &[Action]) -> Option<Action> {
+        actions
+            .iter()
+            .copied()
+            .find(|action| self.key(*action) == key)
+    }
+
+    /// Finds two actions of the same view bound to one key.
+    pub fn conflict(&self) -> Option<(Action, Action)> {
+        for actions in [MENU, SORT, EDIT, COMPLEXITY, SETTINGS] {
+            for (i, a) in actions.iter().enumerate() {
+                if let Some(b) = actions[i + 1..]
+                    .iter()
+                    .find(|b| self.key(**b) == self.key(*a))
+                {
+                    return Some((*a, *b));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Parses a key such as `q`, `enter`, `space` or `f5`.
+pub fn parse_key(text: &str) -> Option<KeyCode> {
+    let mut chars = text.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    Some(match text.to_lowercase().as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "space" => KeyCode::Char(' '),
+        "tab" => KeyCode::Tab,
+        "esc" | "escape" => KeyCode::Esc,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        name => KeyCode::F(name.strip_prefix('f')?.parse().ok()?),
+    })
+}
+
+/// The name `parse_key` reads `key` from.
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Delete => "delete".to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::Home => "home".to_string(),
+        KeyCode::End => "end".to_string(),
+        KeyCode::PageUp => "pageup".to_string(),
+        KeyCode::PageDown => "pagedown".to_string(),
+        KeyCode::F(n) => format!("f{}", n),
+        _ => "?".to_string(),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("x"), Some(KeyCode::Char('x')));
+        assert_eq!(parse_key("Space"), Some(KeyCode::Char(' ')));
+        assert_eq!(parse_key("f5"), Some(KeyCode::F(5)));
+        assert_eq!(parse_key("ctrl"), None);
+        assert_eq!(parse_key("fx"), None);
+        for (_, key) in Keymap::default().bindings {
+            assert_eq!(parse_key(&key_name(key)), Some(key));
+        }
+    }
+
+    #[test]
+    fn test_conflict() {
+        let mut keymap = Keymap::default();
+        assert_eq!(keymap.conflict(), None);
+        assert_eq!(keymap.action(KeyCode::Enter, SORT), Some(Action::Start));
+        keymap.bind(Action::Info, KeyCode::Char('q'));
+        assert_eq!(keymap.conflict(), Some((Action::Quit, Action::Info)));
+    }
+}
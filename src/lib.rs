@@ -0,0 +1,12 @@
+//! The step-by-step sorting engine behind `term-sort`, usable without the
+//! terminal interface: every algorithm precomputes a snapshot of the array
+//! and the operations performed at each step, which can be played back,
+//! measured or saved as a trace.
+
+pub mod complexity;
+pub mod data;
+pub mod metrics;
+pub mod registry;
+pub mod script;
+pub mod sort;
+pub mod trace;
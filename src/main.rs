@@ -1,20 +1,26 @@
 use crate::app::{App, View};
+use crate::config::Config;
+use crate::keymap::Action;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::{
     backtrace::Backtrace,
     io,
-    panic::{self, PanicInfo},
+    panic::{self, PanicHookInfo},
+    process, thread,
     time::{Duration, Instant},
 };
+use term_sort::{registry, script::Script};
 use tui::{backend::CrosstermBackend, layout::Rect, Terminal};
 
 pub mod app;
+pub mod config;
 pub mod handlers;
-pub mod sort;
+pub mod keymap;
+pub mod theme;
 pub mod ui;
 
 fn setup_terminal(stdout: &mut io::Stdout) -> Result<(), io::Error> {
@@ -29,7 +35,11 @@ fn cleanup_terminal() -> Result<(), io::Error> {
     Ok(())
 }
 
-fn handle_panic(info: &PanicInfo<'_>) -> Result<(), io::Error> {
+fn handle_panic(info: &PanicHookInfo<'_>) -> Result<(), io::Error> {
+    // Sorts running on worker threads report their own panics on screen
+    if thread::current().name() != Some("main") {
+        return Ok(());
+    }
     let msg = info.payload().downcast_ref::<&'static str>().unwrap();
     cleanup_terminal()?;
     println!("{}", *msg);
@@ -37,7 +47,7 @@ fn handle_panic(info: &PanicInfo<'_>) -> Result<(), io::Error> {
     Ok(())
 }
 
-fn ui() -> Result<(), io::Error> {
+fn ui(config: Config, start: Option<View>, scripts: &[&'static Script]) -> Result<(), io::Error> {
     // Cleanup terminal on panic
     panic::set_hook(Box::new(|info| {
         handle_panic(info).unwrap();
@@ -52,13 +62,19 @@ fn ui() -> Result<(), io::Error> {
     let mut terminal = Terminal::new(backend)?;
 
     // Initialize the application
-    let mut app = App::new();
+    let mut app = App::new(config);
+    for script in scripts {
+        app.states.menu.as_mut().unwrap().add_script(script);
+    }
+    if let Some(view) = start {
+        app.ui_width = std::cmp::min(terminal.size()?.width, 100) / 2 - 3;
+        app.open(view);
+    }
     let mut last_tick = Instant::now();
-    let tick_rate = Duration::from_millis(12);
 
     // Draw loop
     loop {
-        terminal.draw(|mut f| {
+        terminal.draw(|f| {
             let size = f.size();
             let w = std::cmp::min(size.width, 100);
             let h = std::cmp::min(size.height, 30);
@@ -67,25 +83,30 @@ fn ui() -> Result<(), io::Error> {
                 height: h,
                 x: (size.width - w) / 2,
                 y: (size.height - h) / 2,
-                ..size
             };
 
             app.ui_width = viewport.width / 2 - 3;
             let current_view = app.current_view();
             match current_view {
-                View::Menu => ui::draw_menu(&mut f, &mut app, viewport),
-                _ => ui::draw_single_sort(&mut f, &mut app, viewport),
+                View::Menu => ui::draw_menu(f, &mut app, viewport),
+                View::Complexity => ui::draw_complexity(f, &mut app, viewport),
+                View::Settings => ui::draw_settings_view(f, &mut app, viewport),
+                _ => ui::draw_single_sort(f, &mut app, viewport),
+            }
+            if app.show_help {
+                ui::draw_help(f, &app, viewport);
             }
         })?;
 
         // Handle user input
-        let timeout = tick_rate
+        let timeout = app
+            .speed
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                match key {
+            match event::read()? {
+                Event::Key(key) => match key {
                     event::KeyEvent {
                         code: event::KeyCode::Char('c'),
                         modifiers: event::KeyModifiers::CONTROL,
@@ -93,20 +114,22 @@ fn ui() -> Result<(), io::Error> {
                     } => {
                         break;
                     }
-                    _ if key.code == KeyCode::Char('q') => match app.current_view {
-                        app::View::Menu => break,
-                        _ => app.set_current_view(app::View::Menu),
-                    },
+                    _ if !app.show_help
+                        && app.keymap.action(key.code, &[Action::Quit]).is_some() =>
+                    {
+                        match app.current_view {
+                            app::View::Menu => break,
+                            _ => app.back(),
+                        }
+                    }
                     _ => app.handle_input(key)?,
-                }
+                },
+                Event::Mouse(mouse) => app.handle_mouse(mouse)?,
+                _ => (),
             }
         }
-        if last_tick.elapsed() >= tick_rate {
-            if let Some(sort) = &mut app.sort {
-                if sort.is_active() {
-                    sort.step();
-                }
-            }
+        if last_tick.elapsed() >= app.speed {
+            app.on_tick();
             last_tick = Instant::now();
         }
     }
@@ -117,6 +140,58 @@ fn ui() -> Result<(), io::Error> {
     Ok(())
 }
 
+fn usage_error(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
 fn main() -> Result<(), io::Error> {
-    ui()
+    // `--list` prints the algorithms, `--script FILE` adds a script to the
+    // menu, and an algorithm's id opens it directly. Without an id the first
+    // script is opened, or else the config's default algorithm.
+    let config = Config::load().unwrap_or_else(|error| {
+        // Printed as is, rather than as the debug form `main` would use
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+    let mut start = None;
+    let mut scripts: Vec<&'static Script> = vec![];
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--list" => {
+                for algorithm in registry::ALGORITHMS.iter() {
+                    println!(
+                        "{:<18} {:<16} {}",
+                        algorithm.id,
+                        algorithm.category.get_name(),
+                        algorithm.name
+                    );
+                }
+                return Ok(());
+            }
+            "--script" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| usage_error("--script needs a file".to_string()))?;
+                let script = Script::load(&path).map_err(|error| {
+                    io::Error::new(error.kind(), format!("could not read {}: {}", path, error))
+                })?;
+                // Scripts stay loaded for the whole run
+                scripts.push(Box::leak(Box::new(script)));
+            }
+            id => match registry::find(id) {
+                Some(algorithm) => start = Some(View::Sort(algorithm)),
+                None => {
+                    return Err(usage_error(format!(
+                        "unknown algorithm `{}`, see --list for the ids",
+                        id
+                    )))
+                }
+            },
+        }
+    }
+    let start = start
+        .or(scripts.first().map(|script| View::Script(script)))
+        .or(config.algorithm.map(View::Sort));
+    ui(config, start, &scripts)
 }
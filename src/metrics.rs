@@ -0,0 +1,139 @@
+use crate::sort::final_positions;
+
+/// Measures of how far an array is from sorted.
+pub struct Metrics {
+    pub inversions: usize,
+    pub runs: usize,
+    pub longest_increasing: usize,
+    pub mean_displacement: f64,
+}
+
+impl Metrics {
+    pub fn of(items: &[f64]) -> Metrics {
+        Metrics {
+            inversions: inversions(items),
+            runs: ascending_runs(items),
+            longest_increasing: longest_increasing(items),
+            mean_displacement: mean_displacement(items),
+        }
+    }
+}
+
+/// The inversion count of every step seen so far, for plotting progress.
+#[derive(Default)]
+pub struct MetricsHistory {
+    pub inversions: Vec<u64>,
+}
+
+impl MetricsHistory {
+    /// Records the inversions after `step`. Steps skipped since the last
+    /// update repeat the previous value, and a step earlier than the last
+    /// one means the sort was restarted, so the history starts over.
+    pub fn update(&mut self, step: usize, inversions: usize) {
+        self.inversions.truncate(step);
+        let last = self.inversions.last().copied().unwrap_or(inversions as u64);
+        while self.inversions.len() < step {
+            self.inversions.push(last);
+        }
+        self.inversions.push(inversions as u64);
+    }
+}
+
+/// Counts the pairs of elements that are out of order, by merge sorting a
+/// copy and counting how many elements each one jumps over.
+pub fn inversions(items: &[f64]) -> usize {
+    fn sort_and_count(items: &mut [f64], buffer: &mut Vec<f64>) -> usize {
+        if items.len() < 2 {
+            return 0;
+        }
+        let mid = items.len() / 2;
+        let mut count = sort_and_count(&mut items[..mid], buffer);
+        count += sort_and_count(&mut items[mid..], buffer);
+
+        buffer.clear();
+        let (mut i, mut j) = (0, mid);
+        while i < mid && j < items.len() {
+            if items[j] < items[i] {
+                count += mid - i;
+                buffer.push(items[j]);
+                j += 1;
+            } else {
+                buffer.push(items[i]);
+                i += 1;
+            }
+        }
+        buffer.extend_from_slice(&items[i..mid]);
+        buffer.extend_from_slice(&items[j..]);
+        items.copy_from_slice(buffer);
+        count
+    }
+
+    sort_and_count(&mut items.to_vec(), &mut Vec::with_capacity(items.len()))
+}
+
+/// Counts the maximal non-decreasing runs.
+pub fn ascending_runs(items: &[f64]) -> usize {
+    if items.is_empty() {
+        return 0;
+    }
+    1 + items.windows(2).filter(|pair| pair[1] < pair[0]).count()
+}
+
+/// The length of the longest non-decreasing subsequence, found by patience
+/// sorting onto piles that only keep their top card.
+pub fn longest_increasing(items: &[f64]) -> usize {
+    let mut tops: Vec<f64> = vec![];
+    for &item in items {
+        let pile = tops.partition_point(|&top| top <= item);
+        if pile == tops.len() {
+            tops.push(item);
+        } else {
+            tops[pile] = item;
+        }
+    }
+    tops.len()
+}
+
+/// The average distance between each element and its place once sorted.
+pub fn mean_displacement(items: &[f64]) -> f64 {
+    if items.is_empty() {
+        return 0.0;
+    }
+    let total: usize = final_positions(items)
+        .into_iter()
+        .enumerate()
+        .map(|(index, position)| index.abs_diff(position))
+        .sum();
+    total as f64 / items.len() as f64
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        let metrics = Metrics::of(&[3.0, 1.0, 2.0, 2.0, 0.0]);
+        assert_eq!(metrics.inversions, 7);
+        assert_eq!(metrics.runs, 3);
+        assert_eq!(metrics.longest_increasing, 3);
+        assert_eq!(metrics.mean_displacement, 1.6);
+
+        let sorted = Metrics::of(&[0.0, 1.0, 1.0, 2.0]);
+        assert_eq!(sorted.inversions, 0);
+        assert_eq!(sorted.runs, 1);
+        assert_eq!(sorted.longest_increasing, 4);
+        assert_eq!(sorted.mean_displacement, 0.0);
+    }
+
+    #[test]
+    fn test_history_restarts() {
+        let mut history = MetricsHistory::default();
+        history.update(0, 5);
+        history.update(1, 4);
+        history.update(3, 2);
+        assert_eq!(history.inversions, vec![5, 4, 4, 2]);
+        history.update(0, 7);
+        assert_eq!(history.inversions, vec![7]);
+    }
+}
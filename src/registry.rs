@@ -0,0 +1,445 @@
+use crate::sort::{
+    binary_insertion,
+    bitonic::{self, BitonicSort},
+    bogo, bottom_up_merge, bubble, bucket, circle, cocktail,
+    comb::{self, CombSort},
+    counting, cycle, gnome, heap, in_place_merge, insertion, intro, merge,
+    observed::{ObservedSort, Procedure},
+    odd_even,
+    odd_even_merge::{self, OddEvenMergeSort},
+    pancake, patience, pdq,
+    quick::{self, QuickSort},
+    radix_lsd::{self, LsdRadixSort},
+    radix_msd, selection,
+    shell::{GapSequence, ShellSort},
+    stooge, strand, tim, BasicSort, Build, Info, Sort,
+};
+
+/// The family an algorithm belongs to, by how it moves elements into place.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    Insertion,
+    Selection,
+    Exchange,
+    Merge,
+    Hybrid,
+    Network,
+    Distribution,
+    Esoteric,
+}
+
+impl Category {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Category::Insertion => "Insertion",
+            Category::Selection => "Selection",
+            Category::Exchange => "Exchange",
+            Category::Merge => "Merge",
+            Category::Hybrid => "Hybrid",
+            Category::Network => "Sorting Network",
+            Category::Distribution => "Distribution",
+            Category::Esoteric => "Esoteric",
+        }
+    }
+}
+
+/// How to build a runnable sort for an algorithm.
+pub enum Constructor {
+    /// A sort with no options, run from its snapshot generator alone.
+    Basic { build: Build, recursive: bool },
+    /// A sort written against `ObservedArray`, run on its own thread.
+    Observed(Procedure),
+    /// A sort with state of its own, such as a gap sequence or settings.
+    Custom(fn(Vec<f64>) -> Box<dyn Sort>),
+}
+
+/// Everything the menu, the command line and the complexity view need to
+/// know about an algorithm. `id` is the stable name used on the command line.
+pub struct Algorithm {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub category: Category,
+    pub info: &'static Info,
+    pub constructor: Constructor,
+}
+
+impl Algorithm {
+    pub fn create(&'static self, input: Vec<f64>) -> Box<dyn Sort> {
+        match self.constructor {
+            Constructor::Basic { build, recursive } => Box::new(BasicSort::new(
+                input, self.name, self.info, recursive, build,
+            )),
+            Constructor::Observed(procedure) => {
+                Box::new(ObservedSort::new(input, self.name, self.info, procedure))
+            }
+            Constructor::Custom(create) => create(input),
+        }
+    }
+}
+
+const fn basic(
+    id: &'static str,
+    name: &'static str,
+    category: Category,
+    info: &'static Info,
+    build: Build,
+) -> Algorithm {
+    Algorithm {
+        id,
+        name,
+        category,
+        info,
+        constructor: Constructor::Basic {
+            build,
+            recursive: false,
+        },
+    }
+}
+
+const fn recursive(
+    id: &'static str,
+    name: &'static str,
+    category: Category,
+    info: &'static Info,
+    build: Build,
+) -> Algorithm {
+    Algorithm {
+        id,
+        name,
+        category,
+        info,
+        constructor: Constructor::Basic {
+            build,
+            recursive: true,
+        },
+    }
+}
+
+const fn observed(
+    id: &'static str,
+    name: &'static str,
+    category: Category,
+    info: &'static Info,
+    procedure: Procedure,
+) -> Algorithm {
+    Algorithm {
+        id,
+        name,
+        category,
+        info,
+        constructor: Constructor::Observed(procedure),
+    }
+}
+
+const fn custom(
+    id: &'static str,
+    name: &'static str,
+    category: Category,
+    info: &'static Info,
+    create: fn(Vec<f64>) -> Box<dyn Sort>,
+) -> Algorithm {
+    Algorithm {
+        id,
+        name,
+        category,
+        info,
+        constructor: Constructor::Custom(create),
+    }
+}
+
+/// Every algorithm, in the order the menu lists them by default.
+pub static ALGORITHMS: [Algorithm; 37] = [
+    basic(
+        "insertion",
+        "Insertion Sort",
+        Category::Insertion,
+        &insertion::INFO,
+        insertion::create_iterator,
+    ),
+    basic(
+        "binary-insertion",
+        "Binary Insertion Sort",
+        Category::Insertion,
+        &binary_insertion::INFO,
+        binary_insertion::create_iterator,
+    ),
+    custom(
+        "shell",
+        "Shell Sort (Shell gaps)",
+        Category::Insertion,
+        GapSequence::Shell.info(),
+        |input| Box::new(ShellSort::new(input, GapSequence::Shell)),
+    ),
+    custom(
+        "shell-knuth",
+        "Shell Sort (Knuth gaps)",
+        Category::Insertion,
+        GapSequence::Knuth.info(),
+        |input| Box::new(ShellSort::new(input, GapSequence::Knuth)),
+    ),
+    custom(
+        "shell-sedgewick",
+        "Shell Sort (Sedgewick gaps)",
+        Category::Insertion,
+        GapSequence::Sedgewick.info(),
+        |input| Box::new(ShellSort::new(input, GapSequence::Sedgewick)),
+    ),
+    custom(
+        "shell-ciura",
+        "Shell Sort (Ciura gaps)",
+        Category::Insertion,
+        GapSequence::Ciura.info(),
+        |input| Box::new(ShellSort::new(input, GapSequence::Ciura)),
+    ),
+    custom(
+        "shell-tokuda",
+        "Shell Sort (Tokuda gaps)",
+        Category::Insertion,
+        GapSequence::Tokuda.info(),
+        |input| Box::new(ShellSort::new(input, GapSequence::Tokuda)),
+    ),
+    basic(
+        "selection",
+        "Selection Sort",
+        Category::Selection,
+        &selection::INFO,
+        selection::create_iterator,
+    ),
+    basic(
+        "bubble",
+        "Bubble Sort",
+        Category::Exchange,
+        &bubble::INFO,
+        bubble::create_iterator,
+    ),
+    basic(
+        "cocktail",
+        "Cocktail Shaker Sort",
+        Category::Exchange,
+        &cocktail::INFO,
+        cocktail::create_iterator,
+    ),
+    custom(
+        "comb",
+        "Comb Sort (shrink 1.3)",
+        Category::Exchange,
+        &comb::INFO,
+        |input| Box::new(CombSort::new(input, 1.3)),
+    ),
+    custom(
+        "comb-2",
+        "Comb Sort (shrink 2.0)",
+        Category::Exchange,
+        &comb::INFO,
+        |input| Box::new(CombSort::new(input, 2.0)),
+    ),
+    basic(
+        "gnome",
+        "Gnome Sort",
+        Category::Exchange,
+        &gnome::INFO,
+        gnome::create_iterator,
+    ),
+    basic(
+        "odd-even",
+        "Odd-Even Sort",
+        Category::Exchange,
+        &odd_even::INFO,
+        odd_even::create_iterator,
+    ),
+    observed(
+        "circle",
+        "Circle Sort",
+        Category::Exchange,
+        &circle::INFO,
+        circle::circle_sort,
+    ),
+    basic(
+        "cycle",
+        "Cycle Sort",
+        Category::Selection,
+        &cycle::INFO,
+        cycle::create_iterator,
+    ),
+    recursive(
+        "merge",
+        "Merge Sort",
+        Category::Merge,
+        &merge::INFO,
+        merge::create_iterator,
+    ),
+    basic(
+        "bottom-up-merge",
+        "Bottom-Up Merge Sort",
+        Category::Merge,
+        &bottom_up_merge::INFO,
+        bottom_up_merge::create_iterator,
+    ),
+    recursive(
+        "in-place-merge",
+        "In-Place Merge Sort",
+        Category::Merge,
+        &in_place_merge::INFO,
+        in_place_merge::create_iterator,
+    ),
+    custom(
+        "quick",
+        "Quick Sort",
+        Category::Exchange,
+        &quick::INFO,
+        |input| Box::new(QuickSort::new(input)),
+    ),
+    basic(
+        "heap",
+        "Heap Sort",
+        Category::Selection,
+        &heap::INFO,
+        heap::create_iterator,
+    ),
+    recursive(
+        "tim",
+        "Timsort",
+        Category::Hybrid,
+        &tim::INFO,
+        tim::create_iterator,
+    ),
+    recursive(
+        "intro",
+        "Introsort",
+        Category::Hybrid,
+        &intro::INFO,
+        intro::create_iterator,
+    ),
+    recursive(
+        "pdq",
+        "Pattern-Defeating Quicksort",
+        Category::Hybrid,
+        &pdq::INFO,
+        pdq::create_iterator,
+    ),
+    custom(
+        "bitonic",
+        "Bitonic Sort",
+        Category::Network,
+        &bitonic::INFO,
+        |input| Box::new(BitonicSort::new(input)),
+    ),
+    custom(
+        "odd-even-merge",
+        "Odd-Even Merge Sort",
+        Category::Network,
+        &odd_even_merge::INFO,
+        |input| Box::new(OddEvenMergeSort::new(input)),
+    ),
+    basic(
+        "pancake",
+        "Pancake Sort",
+        Category::Esoteric,
+        &pancake::INFO,
+        pancake::create_iterator,
+    ),
+    recursive(
+        "stooge",
+        "Stooge Sort",
+        Category::Esoteric,
+        &stooge::INFO,
+        stooge::create_iterator,
+    ),
+    basic(
+        "bogo",
+        "Bogosort (capped)",
+        Category::Esoteric,
+        &bogo::INFO,
+        bogo::create_iterator,
+    ),
+    basic(
+        "patience",
+        "Patience Sort",
+        Category::Insertion,
+        &patience::INFO,
+        patience::create_iterator,
+    ),
+    basic(
+        "strand",
+        "Strand Sort",
+        Category::Selection,
+        &strand::INFO,
+        strand::create_iterator,
+    ),
+    basic(
+        "counting",
+        "Counting Sort",
+        Category::Distribution,
+        &counting::INFO,
+        counting::create_iterator,
+    ),
+    custom(
+        "radix-lsd",
+        "LSD Radix Sort (base 10)",
+        Category::Distribution,
+        &radix_lsd::INFO,
+        |input| Box::new(LsdRadixSort::new(input, 10)),
+    ),
+    custom(
+        "radix-lsd-4",
+        "LSD Radix Sort (base 4)",
+        Category::Distribution,
+        &radix_lsd::INFO,
+        |input| Box::new(LsdRadixSort::new(input, 4)),
+    ),
+    custom(
+        "radix-lsd-2",
+        "LSD Radix Sort (base 2)",
+        Category::Distribution,
+        &radix_lsd::INFO,
+        |input| Box::new(LsdRadixSort::new(input, 2)),
+    ),
+    recursive(
+        "radix-msd",
+        "MSD Radix Sort",
+        Category::Distribution,
+        &radix_msd::INFO,
+        radix_msd::create_iterator,
+    ),
+    basic(
+        "bucket",
+        "Bucket Sort",
+        Category::Distribution,
+        &bucket::INFO,
+        bucket::create_iterator,
+    ),
+];
+
+/// Looks an algorithm up by its command line id.
+pub fn find(id: &str) -> Option<&'static Algorithm> {
+    ALGORITHMS.iter().find(|algorithm| algorithm.id == id)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_unique_ids() {
+        for (i, algorithm) in ALGORITHMS.iter().enumerate() {
+            assert!(find(algorithm.id).is_some_and(|found| std::ptr::eq(found, &ALGORITHMS[i])));
+        }
+    }
+
+    #[test]
+    fn test_all_sort() {
+        // Bogosort stops at its step cap long before sorting 20 elements
+        for algorithm in ALGORITHMS.iter().filter(|algorithm| algorithm.id != "bogo") {
+            let mut sort = algorithm.create(generate_random_data(20));
+            while !sort.is_sorted() {
+                sort.step();
+            }
+            assert!(
+                test_util::is_sorted(sort.items()),
+                "{} did not sort",
+                algorithm.name
+            );
+        }
+    }
+}
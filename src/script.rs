@@ -0,0 +1,872 @@
+use crate::sort::{
+    count_operations, Complexity, Info, Operation, Snapshot, Sort, SortPointer, Stepper,
+};
+use std::{collections::HashMap, fmt, fs, io, path::Path};
+
+/// A sorting algorithm written in the built-in scripting language, which
+/// can be loaded at runtime instead of compiled in.
+///
+/// Scripts work on the array through `len()`, `cmp(i, j)` (the sign of
+/// `a[i] - a[j]`), `swap(i, j)`, `get(i)` and `set(i, value)`, and every
+/// comparison, swap and write becomes one step of the visualization:
+///
+/// ```text
+/// # Bubble Sort
+/// for i in 0..len() {
+///     for j in 0..len() - 1 - i {
+///         if cmp(j, j + 1) > 0 {
+///             swap(j, j + 1);
+///         }
+///     }
+/// }
+/// ```
+///
+/// Values are numbers, with comparisons and `&&`, `||` and `!` treating
+/// zero as false. Besides the array functions there are `min`, `max` and
+/// `floor`, `let`, assignment, `if`/`else`, `while`, `for x in lo..hi`,
+/// `break`, `continue`, and functions declared with `fn` that may recurse
+/// and `return` a value. Comments start with `#` or `//`, and a comment on
+/// the first line names the algorithm.
+pub struct Script {
+    pub name: String,
+    pub source: String,
+    /// Where the script was loaded from, so edits are picked up on restart.
+    pub path: Option<String>,
+}
+
+impl Script {
+    pub fn new(name: &str, source: &str) -> Script {
+        Script {
+            name: name.to_string(),
+            source: source.to_string(),
+            path: None,
+        }
+    }
+
+    /// Reads a script, named by its first comment or else its file name.
+    pub fn load(path: &str) -> Result<Script, io::Error> {
+        let source = fs::read_to_string(path)?;
+        let file_name = Path::new(path)
+            .file_stem()
+            .map_or(path.to_string(), |stem| stem.to_string_lossy().to_string());
+        Ok(Script {
+            name: title(&source).unwrap_or(file_name),
+            source,
+            path: Some(path.to_string()),
+        })
+    }
+}
+
+fn title(source: &str) -> Option<String> {
+    let first = source.lines().next()?.trim();
+    let name = first
+        .strip_prefix('#')
+        .or_else(|| first.strip_prefix("//"))?
+        .trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Scripts promise nothing about their behavior.
+pub const INFO: Info = Info {
+    best: Complexity::Unknown,
+    average: Complexity::Unknown,
+    worst: Complexity::Unknown,
+    space: Complexity::Unknown,
+    stable: false,
+    in_place: false,
+    adaptive: false,
+    online: false,
+};
+
+/// The most steps a script may take, so an endless script still finishes.
+pub const STEP_CAP: usize = 100_000;
+/// The most statements a script may run, catching loops without steps.
+const STATEMENT_CAP: usize = 10_000_000;
+const MAX_DEPTH: usize = 256;
+
+/// A problem with a script, and the line (counting from 1) it was found on.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScriptError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+fn error<T>(line: usize, message: String) -> Result<T, ScriptError> {
+    Err(ScriptError { line, message })
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 23] = [
+    "..", "==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "(", ")", "{", "}", ",",
+    ";", "=", "<", ">", "!", ".",
+];
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ScriptError> {
+    let mut tokens = vec![];
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let mut rest = line;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() || rest.starts_with('#') || rest.starts_with("//") {
+                break;
+            }
+            let first = rest.chars().next().unwrap();
+            if first.is_ascii_digit() {
+                let end = rest
+                    .char_indices()
+                    .find(|&(i, c)| {
+                        !(c.is_ascii_digit() || c == '.' && !rest[i..].starts_with(".."))
+                    })
+                    .map_or(rest.len(), |(i, _)| i);
+                match rest[..end].parse() {
+                    Ok(number) => tokens.push((Token::Number(number), line_number)),
+                    Err(_) => return error(line_number, format!("bad number `{}`", &rest[..end])),
+                }
+                rest = &rest[end..];
+            } else if first.is_alphabetic() || first == '_' {
+                let end = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                tokens.push((Token::Name(rest[..end].to_string()), line_number));
+                rest = &rest[end..];
+            } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+                tokens.push((Token::Symbol(symbol), line_number));
+                rest = &rest[symbol.len()..];
+            } else {
+                return error(line_number, format!("unexpected character `{}`", first));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+enum Expr {
+    Number(f64),
+    Variable(String, usize),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>, usize),
+    Call(String, Vec<Expr>, usize),
+}
+
+enum Statement {
+    Let(String, Expr),
+    Assign(String, Expr),
+    If(Expr, Block, Block),
+    While(Expr, Block),
+    For(String, Expr, Expr, Block),
+    Return(Option<Expr>),
+    Break,
+    Continue,
+    Expr(Expr),
+}
+
+/// Statements along with the line each starts on.
+type Block = Vec<(usize, Statement)>;
+
+struct Function {
+    parameters: Vec<String>,
+    body: Block,
+}
+
+struct Program {
+    functions: HashMap<String, Function>,
+    body: Block,
+}
+
+const KEYWORDS: [&str; 10] = [
+    "let", "if", "else", "while", "for", "in", "fn", "return", "break", "continue",
+];
+
+/// Binary operators from loosest to tightest binding.
+const PRECEDENCE: [&[&str]; 5] = [
+    &["||"],
+    &["&&"],
+    &["==", "!=", "<", "<=", ">", ">="],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn at_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol)
+    }
+
+    fn at_name(&self, name: &str) -> bool {
+        matches!(self.peek(), Some(Token::Name(n)) if n == name)
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), ScriptError> {
+        if self.at_symbol(symbol) {
+            self.position += 1;
+            Ok(())
+        } else {
+            error(self.line(), format!("expected `{}`", symbol))
+        }
+    }
+
+    fn expect_name(&mut self) -> Result<String, ScriptError> {
+        match self.next() {
+            Some(Token::Name(name)) if !KEYWORDS.contains(&name.as_str()) => Ok(name),
+            _ => {
+                self.position -= 1;
+                error(self.line(), "expected a name".to_string())
+            }
+        }
+    }
+
+    fn program(&mut self) -> Result<Program, ScriptError> {
+        let mut program = Program {
+            functions: HashMap::new(),
+            body: vec![],
+        };
+        while self.peek().is_some() {
+            if self.at_name("fn") {
+                self.position += 1;
+                let name = self.expect_name()?;
+                self.expect_symbol("(")?;
+                let mut parameters = vec![];
+                while !self.at_symbol(")") {
+                    parameters.push(self.expect_name()?);
+                    if !self.at_symbol(")") {
+                        self.expect_symbol(",")?;
+                    }
+                }
+                self.expect_symbol(")")?;
+                let body = self.block()?;
+                program
+                    .functions
+                    .insert(name, Function { parameters, body });
+            } else {
+                program.body.push(self.statement()?);
+            }
+        }
+        Ok(program)
+    }
+
+    fn block(&mut self) -> Result<Block, ScriptError> {
+        self.expect_symbol("{")?;
+        let mut statements = vec![];
+        while !self.at_symbol("}") {
+            if self.peek().is_none() {
+                return error(self.line(), "expected `}`".to_string());
+            }
+            statements.push(self.statement()?);
+        }
+        self.position += 1;
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<(usize, Statement), ScriptError> {
+        let line = self.line();
+        let keyword = match self.peek() {
+            Some(Token::Name(name)) => name.clone(),
+            _ => String::new(),
+        };
+        let statement = match keyword.as_str() {
+            "let" => {
+                self.position += 1;
+                let name = self.expect_name()?;
+                self.expect_symbol("=")?;
+                Statement::Let(name, self.expression()?)
+            }
+            "if" => return Ok((line, self.if_statement()?)),
+            "while" => {
+                self.position += 1;
+                let condition = self.expression()?;
+                return Ok((line, Statement::While(condition, self.block()?)));
+            }
+            "for" => {
+                self.position += 1;
+                let name = self.expect_name()?;
+                if !self.at_name("in") {
+                    return error(self.line(), "expected `in`".to_string());
+                }
+                self.position += 1;
+                let lo = self.expression()?;
+                self.expect_symbol("..")?;
+                let hi = self.expression()?;
+                return Ok((line, Statement::For(name, lo, hi, self.block()?)));
+            }
+            "return" => {
+                self.position += 1;
+                if self.at_symbol(";") {
+                    Statement::Return(None)
+                } else {
+                    Statement::Return(Some(self.expression()?))
+                }
+            }
+            "break" => {
+                self.position += 1;
+                Statement::Break
+            }
+            "continue" => {
+                self.position += 1;
+                Statement::Continue
+            }
+            _ => {
+                let is_assignment = matches!(
+                    self.tokens.get(self.position + 1),
+                    Some((Token::Symbol("="), _))
+                );
+                if is_assignment {
+                    let name = self.expect_name()?;
+                    self.position += 1;
+                    Statement::Assign(name, self.expression()?)
+                } else {
+                    Statement::Expr(self.expression()?)
+                }
+            }
+        };
+        self.expect_symbol(";")?;
+        Ok((line, statement))
+    }
+
+    fn if_statement(&mut self) -> Result<Statement, ScriptError> {
+        self.position += 1;
+        let condition = self.expression()?;
+        let then = self.block()?;
+        let otherwise = if self.at_name("else") {
+            self.position += 1;
+            if self.at_name("if") {
+                vec![(self.line(), self.if_statement()?)]
+            } else {
+                self.block()?
+            }
+        } else {
+            vec![]
+        };
+        Ok(Statement::If(condition, then, otherwise))
+    }
+
+    fn expression(&mut self) -> Result<Expr, ScriptError> {
+        self.binary(0)
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, ScriptError> {
+        if level == PRECEDENCE.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(Token::Symbol(symbol)) = self.peek() {
+            let symbol: &'static str = symbol;
+            if !PRECEDENCE[level].contains(&symbol) {
+                break;
+            }
+            let line = self.line();
+            self.position += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(symbol, Box::new(left), Box::new(right), line);
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ScriptError> {
+        for symbol in ["-", "!"] {
+            if self.at_symbol(symbol) {
+                self.position += 1;
+                return Ok(Expr::Unary(symbol, Box::new(self.unary()?)));
+            }
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, ScriptError> {
+        let line = self.line();
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Expr::Number(number)),
+            Some(Token::Name(name)) if name == "true" => Ok(Expr::Number(1.0)),
+            Some(Token::Name(name)) if name == "false" => Ok(Expr::Number(0.0)),
+            Some(Token::Name(name)) if !KEYWORDS.contains(&name.as_str()) => {
+                if !self.at_symbol("(") {
+                    return Ok(Expr::Variable(name, line));
+                }
+                self.position += 1;
+                let mut arguments = vec![];
+                while !self.at_symbol(")") {
+                    arguments.push(self.expression()?);
+                    if !self.at_symbol(")") {
+                        self.expect_symbol(",")?;
+                    }
+                }
+                self.position += 1;
+                Ok(Expr::Call(name, arguments, line))
+            }
+            Some(Token::Symbol("(")) => {
+                let inner = self.expression()?;
+                self.expect_symbol(")")?;
+                Ok(inner)
+            }
+            _ => error(line, "expected an expression".to_string()),
+        }
+    }
+}
+
+fn parse(source: &str) -> Result<Program, ScriptError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+    };
+    parser.program()
+}
+
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+    Return(f64),
+}
+
+/// Runs a program against the array, recording a snapshot at every
+/// comparison, swap and write.
+struct Machine<'a> {
+    functions: &'a HashMap<String, Function>,
+    items: Vec<f64>,
+    result: Vec<Snapshot>,
+    statements: usize,
+    depth: usize,
+}
+
+impl Machine<'_> {
+    fn record(
+        &mut self,
+        pointer: SortPointer,
+        operation: Operation,
+        line: usize,
+    ) -> Result<(), ScriptError> {
+        if self.result.len() >= STEP_CAP {
+            return error(line, format!("took more than {} steps", STEP_CAP));
+        }
+        self.result
+            .push(Snapshot::new(self.items.clone(), pointer).with_operations(vec![operation]));
+        Ok(())
+    }
+
+    fn index(&self, value: f64, line: usize) -> Result<usize, ScriptError> {
+        if value.fract() != 0.0 || value < 0.0 {
+            return error(line, format!("{} is not an index", value));
+        }
+        let index = value as usize;
+        if index >= self.items.len() {
+            return error(
+                line,
+                format!(
+                    "index {} is out of range for length {}",
+                    index,
+                    self.items.len()
+                ),
+            );
+        }
+        Ok(index)
+    }
+
+    fn run(
+        &mut self,
+        block: &Block,
+        scope: &mut HashMap<String, f64>,
+    ) -> Result<Flow, ScriptError> {
+        for (line, statement) in block {
+            let line = *line;
+            self.tick(line)?;
+            let flow = match statement {
+                Statement::Let(name, value) => {
+                    let value = self.evaluate(value, scope)?;
+                    scope.insert(name.clone(), value);
+                    Flow::Normal
+                }
+                Statement::Assign(name, value) => {
+                    let value = self.evaluate(value, scope)?;
+                    match scope.get_mut(name) {
+                        Some(variable) => *variable = value,
+                        None => return error(line, format!("unknown variable `{}`", name)),
+                    }
+                    Flow::Normal
+                }
+                Statement::If(condition, then, otherwise) => {
+                    if self.evaluate(condition, scope)? != 0.0 {
+                        self.run(then, scope)?
+                    } else {
+                        self.run(otherwise, scope)?
+                    }
+                }
+                Statement::While(condition, body) => {
+                    let mut flow = Flow::Normal;
+                    while self.evaluate(condition, scope)? != 0.0 {
+                        self.tick(line)?;
+                        match self.run(body, scope)? {
+                            Flow::Break => break,
+                            Flow::Return(value) => {
+                                flow = Flow::Return(value);
+                                break;
+                            }
+                            Flow::Normal | Flow::Continue => (),
+                        }
+                    }
+                    flow
+                }
+                Statement::For(name, lo, hi, body) => {
+                    let mut value = self.evaluate(lo, scope)?;
+                    let hi = self.evaluate(hi, scope)?;
+                    let mut flow = Flow::Normal;
+                    while value < hi {
+                        self.tick(line)?;
+                        scope.insert(name.clone(), value);
+                        match self.run(body, scope)? {
+                            Flow::Break => break,
+                            Flow::Return(value) => {
+                                flow = Flow::Return(value);
+                                break;
+                            }
+                            Flow::Normal | Flow::Continue => (),
+                        }
+                        value += 1.0;
+                    }
+                    flow
+                }
+                Statement::Return(value) => match value {
+                    Some(value) => Flow::Return(self.evaluate(value, scope)?),
+                    None => Flow::Return(0.0),
+                },
+                Statement::Break => Flow::Break,
+                Statement::Continue => Flow::Continue,
+                Statement::Expr(expr) => {
+                    self.evaluate(expr, scope)?;
+                    Flow::Normal
+                }
+            };
+            if !matches!(flow, Flow::Normal) {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    /// Counts a statement or loop iteration against the statement cap, so
+    /// loops that take no steps cannot spin forever.
+    fn tick(&mut self, line: usize) -> Result<(), ScriptError> {
+        self.statements += 1;
+        if self.statements > STATEMENT_CAP {
+            return error(line, "ran for too long".to_string());
+        }
+        Ok(())
+    }
+
+    fn evaluate(
+        &mut self,
+        expr: &Expr,
+        scope: &mut HashMap<String, f64>,
+    ) -> Result<f64, ScriptError> {
+        let truth = |value: bool| if value { 1.0 } else { 0.0 };
+        match expr {
+            Expr::Number(number) => Ok(*number),
+            Expr::Variable(name, line) => match scope.get(name) {
+                Some(value) => Ok(*value),
+                None => error(*line, format!("unknown variable `{}`", name)),
+            },
+            Expr::Unary(symbol, inner) => {
+                let value = self.evaluate(inner, scope)?;
+                Ok(if *symbol == "-" {
+                    -value
+                } else {
+                    truth(value == 0.0)
+                })
+            }
+            // Logical operators only evaluate their right side when needed
+            Expr::Binary("&&", left, right, _) => Ok(truth(
+                self.evaluate(left, scope)? != 0.0 && self.evaluate(right, scope)? != 0.0,
+            )),
+            Expr::Binary("||", left, right, _) => Ok(truth(
+                self.evaluate(left, scope)? != 0.0 || self.evaluate(right, scope)? != 0.0,
+            )),
+            Expr::Binary(symbol, left, right, line) => {
+                let a = self.evaluate(left, scope)?;
+                let b = self.evaluate(right, scope)?;
+                match *symbol {
+                    "+" => Ok(a + b),
+                    "-" => Ok(a - b),
+                    "*" => Ok(a * b),
+                    "/" | "%" if b == 0.0 => error(*line, "division by zero".to_string()),
+                    "/" => Ok(a / b),
+                    "%" => Ok(a % b),
+                    "==" => Ok(truth(a == b)),
+                    "!=" => Ok(truth(a != b)),
+                    "<" => Ok(truth(a < b)),
+                    "<=" => Ok(truth(a <= b)),
+                    ">" => Ok(truth(a > b)),
+                    _ => Ok(truth(a >= b)),
+                }
+            }
+            Expr::Call(name, arguments, line) => {
+                let line = *line;
+                let mut values = vec![];
+                for argument in arguments {
+                    values.push(self.evaluate(argument, scope)?);
+                }
+                self.call(name, &values, line)
+            }
+        }
+    }
+
+    fn call(&mut self, name: &str, arguments: &[f64], line: usize) -> Result<f64, ScriptError> {
+        let expected = match name {
+            "len" => 0,
+            "get" | "floor" => 1,
+            "cmp" | "swap" | "set" | "min" | "max" => 2,
+            _ => match self.functions.get(name) {
+                Some(function) => function.parameters.len(),
+                None => return error(line, format!("unknown function `{}`", name)),
+            },
+        };
+        if arguments.len() != expected {
+            return error(
+                line,
+                format!(
+                    "`{}` takes {} arguments, not {}",
+                    name,
+                    expected,
+                    arguments.len()
+                ),
+            );
+        }
+        match name {
+            "len" => Ok(self.items.len() as f64),
+            "get" => Ok(self.items[self.index(arguments[0], line)?]),
+            "floor" => Ok(arguments[0].floor()),
+            "min" => Ok(arguments[0].min(arguments[1])),
+            "max" => Ok(arguments[0].max(arguments[1])),
+            "cmp" => {
+                let (i, j) = (
+                    self.index(arguments[0], line)?,
+                    self.index(arguments[1], line)?,
+                );
+                self.record(SortPointer(i, j), Operation::Compare(i, j), line)?;
+                Ok(match self.items[i].total_cmp(&self.items[j]) {
+                    std::cmp::Ordering::Less => -1.0,
+                    std::cmp::Ordering::Equal => 0.0,
+                    std::cmp::Ordering::Greater => 1.0,
+                })
+            }
+            "swap" => {
+                let (i, j) = (
+                    self.index(arguments[0], line)?,
+                    self.index(arguments[1], line)?,
+                );
+                self.items.swap(i, j);
+                self.record(SortPointer(i, j), Operation::Swap(i, j), line)?;
+                Ok(0.0)
+            }
+            "set" => {
+                let i = self.index(arguments[0], line)?;
+                self.items[i] = arguments[1];
+                self.record(SortPointer(i, i), Operation::Write(i), line)?;
+                Ok(0.0)
+            }
+            _ => {
+                if self.depth >= MAX_DEPTH {
+                    return error(line, format!("recursed deeper than {} calls", MAX_DEPTH));
+                }
+                let function = &self.functions[name];
+                let mut scope: HashMap<String, f64> = function
+                    .parameters
+                    .iter()
+                    .cloned()
+                    .zip(arguments.iter().copied())
+                    .collect();
+                self.depth += 1;
+                let flow = self.run(&function.body, &mut scope);
+                self.depth -= 1;
+                match flow? {
+                    Flow::Return(value) => Ok(value),
+                    Flow::Normal => Ok(0.0),
+                    Flow::Break | Flow::Continue => error(
+                        line,
+                        format!("`break` or `continue` outside a loop in `{}`", name),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Runs `source` on `input`, returning the snapshots of every step. When the
+/// script fails, the steps taken up to the failure are returned with the
+/// error.
+pub fn run(source: &str, input: &mut [f64]) -> (Vec<Snapshot>, Option<ScriptError>) {
+    let program = match parse(source) {
+        Ok(program) => program,
+        Err(error) => return (vec![], Some(error)),
+    };
+    let mut machine = Machine {
+        functions: &program.functions,
+        items: input.to_vec(),
+        result: vec![],
+        statements: 0,
+        depth: 0,
+    };
+    let outcome = match machine.run(&program.body, &mut HashMap::new()) {
+        Ok(Flow::Break | Flow::Continue) => {
+            error(0, "`break` or `continue` outside a loop".to_string())
+        }
+        Ok(_) => Ok(()),
+        Err(error) => Err(error),
+    };
+    input.copy_from_slice(&machine.items);
+    (machine.result, outcome.err())
+}
+
+pub struct ScriptSort {
+    pub stepper: Stepper,
+    pub name: String,
+    pub source: String,
+    pub path: Option<String>,
+    pub error: Option<ScriptError>,
+}
+
+impl ScriptSort {
+    pub fn new(input: Vec<f64>, script: &Script) -> ScriptSort {
+        let (result, error) = run(&script.source, &mut input.clone());
+        ScriptSort {
+            stepper: Stepper::new(input, count_operations(result)),
+            name: script.name.clone(),
+            source: script.source.clone(),
+            path: script.path.clone(),
+            error,
+        }
+    }
+}
+
+impl Sort for ScriptSort {
+    fn stepper(&self) -> &Stepper {
+        &self.stepper
+    }
+    fn stepper_mut(&mut self) -> &mut Stepper {
+        &mut self.stepper
+    }
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+    fn info(&self) -> &'static Info {
+        &INFO
+    }
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+        count_operations(run(&self.source, input).0)
+    }
+    /// Rereads the script from disk, so it can be edited between runs.
+    fn reset(&mut self, items: Vec<f64>) {
+        if let Some(path) = &self.path {
+            match fs::read_to_string(path) {
+                Ok(source) => self.source = source,
+                Err(error) => {
+                    self.error = Some(ScriptError {
+                        line: 0,
+                        message: format!("could not reread {}: {}", path, error),
+                    });
+                    return;
+                }
+            }
+        }
+        let (result, error) = run(&self.source, &mut items.clone());
+        self.error = error;
+        self.stepper.restart(items, count_operations(result));
+    }
+    fn error(&self) -> Option<String> {
+        self.error.as_ref().map(|error| error.to_string())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    const BUBBLE: &str = include_str!("../scripts/bubble.sort");
+    const QUICK: &str = include_str!("../scripts/quick.sort");
+
+    #[test]
+    fn test_final_sort() {
+        for source in [BUBBLE, QUICK, include_str!("../scripts/insertion.sort")] {
+            let mut items = generate_random_data(100);
+            let (result, error) = run(source, &mut items);
+            assert_eq!(error, None);
+            assert!(test_util::is_sorted(&result.last().unwrap().items));
+            assert!(test_util::is_sorted(&items));
+        }
+    }
+
+    #[test]
+    fn test_same_steps_as_native() {
+        let items = generate_random_data(30);
+        let (script, _) = run(BUBBLE, &mut items.clone());
+        let native: Vec<Snapshot> =
+            crate::sort::bubble::create_iterator(&mut items.clone()).collect();
+        let operations: Vec<Operation> = script.iter().flat_map(|s| s.operations.clone()).collect();
+        let native_operations: Vec<Operation> =
+            native.iter().flat_map(|s| s.operations.clone()).collect();
+        assert_eq!(operations, native_operations);
+    }
+
+    #[test]
+    fn test_errors() {
+        let run_error = |source: &str| run(source, &mut [2.0, 1.0]).1.unwrap();
+        assert_eq!(run_error("let x = ;").line, 1);
+        assert_eq!(
+            run_error("swap(0, 1);\nswap(0, 2);").message,
+            "index 2 is out of range for length 2"
+        );
+        assert_eq!(
+            run_error("\n\ny = 1;").to_string(),
+            "line 3: unknown variable `y`"
+        );
+        assert!(run_error("while true { }").message.contains("too long"));
+        assert!(run_error("fn f(x) { return f(x); }\nf(1);")
+            .message
+            .contains("recursed"));
+        // Steps before the failure are kept
+        assert_eq!(run("swap(0, 1);\nfoo();", &mut [2.0, 1.0]).0.len(), 1);
+    }
+
+    #[test]
+    fn test_title() {
+        assert_eq!(
+            title("# Bubble Sort\nswap(0, 1);"),
+            Some("Bubble Sort".to_string())
+        );
+        assert_eq!(title("swap(0, 1);"), None);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::sort::{
+    count_operations, insertion::binary_insertion_sort_range, Complexity, Info, Snapshot,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Constant,
+    stable: true,
+    in_place: true,
+    adaptive: true,
+    online: true,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let len = input.len();
+    binary_insertion_sort_range(input, 0..len, &|snapshot| snapshot, &mut result);
+    count_operations(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, insertion, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_fewer_comparisons() {
+        let items = generate_random_data(100);
+        let linear = insertion::create_iterator(&mut items.clone())
+            .last()
+            .unwrap();
+        let binary = create_iterator(&mut items.clone()).last().unwrap();
+        assert!(binary.counters.comparisons <= linear.counters.comparisons);
+        assert_eq!(binary.counters.swaps, linear.counters.swaps);
+    }
+}
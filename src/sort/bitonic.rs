@@ -0,0 +1,118 @@
+use crate::sort::{
+    count_operations,
+    network::{apply_network, truncate_network},
+    Complexity, Info, Network, Snapshot, Sort, Stepper,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogSquaredN,
+    average: Complexity::NLogSquaredN,
+    worst: Complexity::NLogSquaredN,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub struct BitonicSort {
+    pub stepper: Stepper,
+    pub network: Network,
+}
+
+impl BitonicSort {
+    pub fn new(input: Vec<f64>) -> BitonicSort {
+        let iterator = create_iterator(&mut input.clone());
+        BitonicSort {
+            network: network(input.len()),
+            stepper: Stepper::new(input, iterator),
+        }
+    }
+}
+
+impl Sort for BitonicSort {
+    fn stepper(&self) -> &Stepper {
+        &self.stepper
+    }
+    fn stepper_mut(&mut self) -> &mut Stepper {
+        &mut self.stepper
+    }
+    fn get_name(&self) -> String {
+        "Bitonic Sort".to_string()
+    }
+    fn info(&self) -> &'static Info {
+        &INFO
+    }
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+        create_iterator(input)
+    }
+    fn reset(&mut self, items: Vec<f64>) {
+        self.network = network(items.len());
+        let iterator = self.create_iterator(&mut items.clone());
+        self.stepper.restart(items, iterator);
+    }
+    fn network(&self) -> Option<&Network> {
+        Some(&self.network)
+    }
+}
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let network = network(input.len());
+    count_operations(apply_network(input, &network))
+}
+
+/// Builds a bitonic sorting network for `len` elements. Each merge starts by
+/// comparing elements mirrored across the middle of the block, which keeps
+/// every comparator pointing the same way.
+pub fn network(len: usize) -> Network {
+    let size = len.next_power_of_two();
+    let mut network = vec![];
+    let mut block = 2;
+    while block <= size {
+        network.push(
+            (0..size)
+                .map(|i| (i, i ^ (block - 1)))
+                .filter(|&(i, j)| i < j)
+                .collect(),
+        );
+        let mut distance = block / 4;
+        while distance > 0 {
+            network.push(
+                (0..size)
+                    .map(|i| (i, i ^ distance))
+                    .filter(|&(i, j)| i < j)
+                    .collect(),
+            );
+            distance /= 2;
+        }
+        block *= 2;
+    }
+    truncate_network(network, len)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_layers_are_independent() {
+        for layer in network(100) {
+            let mut seen: Vec<usize> = layer.iter().flat_map(|&(i, j)| [i, j]).collect();
+            let len = seen.len();
+            seen.sort();
+            seen.dedup();
+            assert_eq!(seen.len(), len);
+            assert!(layer.iter().all(|&(i, j)| i < j && j < 100));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::sort::{count_operations, Complexity, Info, Operation, Snapshot, SortPointer};
+
+pub const INFO: Info = Info {
+    best: Complexity::Linear,
+    average: Complexity::Factorial,
+    worst: Complexity::Unbounded,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+/// Bogosort would shuffle forever on anything but tiny inputs, so it gives
+/// up after this many steps.
+pub const STEP_CAP: usize = 5000;
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    use rand::Rng;
+
+    let mut result = vec![];
+    let mut rng = rand::thread_rng();
+    loop {
+        if check_sorted(input, &mut result) {
+            break;
+        }
+        if result.len() >= STEP_CAP {
+            result.push(
+                Snapshot::new(input.to_vec(), SortPointer::none()).with_phase("Step Cap Reached"),
+            );
+            break;
+        }
+
+        let mut operations = vec![];
+        for i in (1..input.len()).rev() {
+            let j = rng.gen_range(0..=i);
+            if i != j {
+                input.swap(i, j);
+                operations.push(Operation::Swap(i, j));
+            }
+        }
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer::none())
+                .with_operations(operations)
+                .with_phase("Shuffle"),
+        );
+    }
+    count_operations(result)
+}
+
+/// Walks the array until the first pair out of order, or until the step cap.
+fn check_sorted(input: &[f64], result: &mut Vec<Snapshot>) -> bool {
+    for i in 1..input.len() {
+        if result.len() >= STEP_CAP {
+            return false;
+        }
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer(i - 1, i))
+                .with_operations(vec![Operation::Compare(i - 1, i)])
+                .with_phase("Check"),
+        );
+        if input[i] < input[i - 1] {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::test_util;
+
+    #[test]
+    fn test_step_cap() {
+        let mut items: Vec<f64> = (0..100).rev().map(|x| x as f64).collect();
+        let snapshots: Vec<Snapshot> = create_iterator(&mut items).collect();
+        assert!(snapshots.len() <= STEP_CAP + 1);
+        assert_eq!(snapshots.last().unwrap().phase, Some("Step Cap Reached"));
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        for mut items in [vec![], vec![1.0], vec![2.0, 1.0], vec![3.0, 1.0, 2.0, 1.0]] {
+            create_iterator(&mut items).for_each(drop);
+            assert!(test_util::is_sorted(&items));
+        }
+    }
+}
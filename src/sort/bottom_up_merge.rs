@@ -0,0 +1,64 @@
+use crate::sort::{
+    count_operations, merge::merge, AuxArray, CallStack, Complexity, Info, Snapshot,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::NLogN,
+    worst: Complexity::NLogN,
+    space: Complexity::Linear,
+    stable: true,
+    in_place: false,
+    adaptive: false,
+    online: false,
+};
+
+/// Merges neighbouring runs of width 1, 2, 4 and so on, without recursion.
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let len = input.len();
+    let mut buffer = AuxArray::new("Buffer", len);
+    let mut width = 1;
+    while width < len {
+        for lo in (0..len - width).step_by(2 * width) {
+            let mid = lo + width;
+            let hi = (mid + width).min(len);
+            // A single frame marks the runs being merged on the chart
+            let mut runs = CallStack::default();
+            runs.push(lo, hi);
+            merge(
+                input,
+                &mut buffer,
+                lo,
+                mid,
+                hi,
+                &|snapshot| snapshot.with_stack(runs.clone()),
+                &mut result,
+            );
+        }
+        width *= 2;
+    }
+    count_operations(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_peak_aux_memory() {
+        let items = generate_random_data(100);
+        let snapshot = create_iterator(&mut items.clone()).last().unwrap();
+        assert_eq!(snapshot.counters.peak_aux_memory, 100);
+    }
+}
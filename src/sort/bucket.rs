@@ -0,0 +1,88 @@
+use crate::sort::{
+    count_operations, insertion::insertion_sort_range, Complexity, Histogram, Info, Operation,
+    Snapshot, SortPointer,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::LinearKeys,
+    average: Complexity::LinearKeys,
+    worst: Complexity::Quadratic,
+    space: Complexity::LinearKeys,
+    stable: true,
+    in_place: false,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let min = input.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = input.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let mut histogram = Histogram::new("Bucket Sizes".to_string(), BUCKETS);
+    let mut buckets: Vec<Vec<f64>> = vec![vec![]; BUCKETS];
+
+    for i in 0..input.len() {
+        let bucket = ((input[i] - min) / (max - min + 1.0) * BUCKETS as f64) as usize;
+        buckets[bucket].push(input[i]);
+        histogram.counts[bucket] += 1;
+        histogram.current = Some(bucket);
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer(i, i))
+                .with_operations(vec![Operation::Distribute { index: i, bucket }])
+                .with_histogram(histogram.clone())
+                .with_phase("Scatter")
+                .with_aux_memory(i + 1),
+        );
+    }
+
+    let mut k = 0;
+    for (bucket, values) in buckets.iter().enumerate() {
+        histogram.current = Some(bucket);
+        for value in values {
+            input[k] = *value;
+            result.push(
+                Snapshot::new(input.to_vec(), SortPointer(k, k))
+                    .with_operations(vec![Operation::Write(k)])
+                    .with_histogram(histogram.clone())
+                    .with_phase("Gather")
+                    .with_aux_memory(input.len() - k - 1),
+            );
+            k += 1;
+        }
+    }
+
+    // Insertion sort each bucket's run of the array
+    let mut lo = 0;
+    for (bucket, values) in buckets.iter().enumerate() {
+        histogram.current = Some(bucket);
+        insertion_sort_range(
+            input,
+            lo..lo + values.len(),
+            &|snapshot| {
+                snapshot
+                    .with_histogram(histogram.clone())
+                    .with_phase("Insertion")
+            },
+            &mut result,
+        );
+        lo += values.len();
+    }
+    count_operations(result)
+}
+
+const BUCKETS: usize = 10;
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+}
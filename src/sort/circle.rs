@@ -0,0 +1,73 @@
+use crate::sort::{observed::ObservedArray, Complexity, Info};
+use std::cmp::Ordering;
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::NLogN,
+    worst: Complexity::NLogSquaredN,
+    space: Complexity::Log,
+    stable: false,
+    in_place: true,
+    adaptive: true,
+    online: false,
+};
+
+/// Compares and swaps mirrored pairs from the ends of the range inwards,
+/// then does the same to both halves, repeating until a pass swaps nothing.
+pub fn circle_sort(array: &mut ObservedArray) {
+    if array.is_empty() {
+        return;
+    }
+    while circle(array, 0, array.len() - 1) {}
+}
+
+fn circle(array: &mut ObservedArray, lo: usize, hi: usize) -> bool {
+    if lo == hi {
+        return false;
+    }
+    let mut swapped = false;
+    let (mut i, mut j) = (lo, hi);
+    while i < j {
+        if array.compare(i, j) == Ordering::Greater {
+            array.swap(i, j);
+            swapped = true;
+        }
+        i += 1;
+        j -= 1;
+    }
+    // With an odd length the middle element is compared with its neighbour
+    if i == j && j < hi && array.compare(i, j + 1) == Ordering::Greater {
+        array.swap(i, j + 1);
+        swapped = true;
+    }
+    let mid = lo + (hi - lo) / 2;
+    let left = circle(array, lo, mid);
+    let right = circle(array, mid + 1, hi);
+    swapped || left || right
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::observed::{observe, Run};
+    use crate::sort::{generate_random_data, test_util, Operation, Snapshot};
+
+    fn run(items: &[f64]) -> Vec<Snapshot> {
+        observe(circle_sort, items, Run::default()).collect()
+    }
+
+    #[test]
+    fn test_final_sort() {
+        let snapshot = run(&generate_random_data(100)).pop().unwrap();
+        assert!(test_util::is_sorted(&snapshot.items));
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        assert!(run(&[]).is_empty());
+        assert!(run(&[1.0]).is_empty());
+        let snapshots = run(&[2.0, 1.0]);
+        assert_eq!(snapshots[1].operations, vec![Operation::Swap(0, 1)]);
+        assert_eq!(snapshots.last().unwrap().items, vec![1.0, 2.0]);
+    }
+}
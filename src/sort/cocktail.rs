@@ -0,0 +1,65 @@
+use crate::sort::{count_operations, Complexity, Info, Operation, Snapshot, SortPointer};
+
+pub const INFO: Info = Info {
+    best: Complexity::Linear,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Constant,
+    stable: true,
+    in_place: true,
+    adaptive: true,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let (mut lo, mut hi) = (0, input.len().saturating_sub(1));
+    let mut swapped = true;
+    while swapped && lo < hi {
+        swapped = false;
+        for j in lo..hi {
+            swapped |= compare_exchange(input, j, "Forward", &mut result);
+        }
+        hi -= 1;
+        for j in (lo..hi).rev() {
+            swapped |= compare_exchange(input, j, "Backward", &mut result);
+        }
+        lo += 1;
+    }
+    count_operations(result)
+}
+
+fn compare_exchange(
+    input: &mut [f64],
+    j: usize,
+    phase: &'static str,
+    result: &mut Vec<Snapshot>,
+) -> bool {
+    let mut operations = vec![Operation::Compare(j, j + 1)];
+    let swapped = input[j] > input[j + 1];
+    if swapped {
+        input.swap(j, j + 1);
+        operations.push(Operation::Swap(j, j + 1));
+    }
+    result.push(
+        Snapshot::new(input.to_vec(), SortPointer(j, j + 1))
+            .with_operations(operations)
+            .with_phase(phase),
+    );
+    swapped
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+}
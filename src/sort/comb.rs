@@ -0,0 +1,95 @@
+use crate::sort::{
+    count_operations, Complexity, Info, Operation, Snapshot, Sort, SortPointer, Stepper,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub struct CombSort {
+    pub stepper: Stepper,
+    pub shrink: f64,
+}
+
+impl CombSort {
+    pub fn new(input: Vec<f64>, shrink: f64) -> CombSort {
+        let iterator = create_iterator(&mut input.clone(), shrink);
+        CombSort {
+            stepper: Stepper::new(input, iterator),
+            shrink,
+        }
+    }
+}
+
+impl Sort for CombSort {
+    fn stepper(&self) -> &Stepper {
+        &self.stepper
+    }
+    fn stepper_mut(&mut self) -> &mut Stepper {
+        &mut self.stepper
+    }
+    fn get_name(&self) -> String {
+        format!("Comb Sort (shrink {})", self.shrink)
+    }
+    fn info(&self) -> &'static Info {
+        &INFO
+    }
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+        create_iterator(input, self.shrink)
+    }
+}
+
+pub fn create_iterator(input: &mut [f64], shrink: f64) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let mut gap = input.len();
+    let mut swapped = true;
+    while gap > 1 || swapped {
+        gap = ((gap as f64 / shrink) as usize).max(1);
+        swapped = false;
+        for i in 0..input.len().saturating_sub(gap) {
+            let j = i + gap;
+            let mut operations = vec![Operation::Compare(i, j)];
+            if input[i] > input[j] {
+                input.swap(i, j);
+                operations.push(Operation::Swap(i, j));
+                swapped = true;
+            }
+            result.push(
+                Snapshot::new(input.to_vec(), SortPointer(i, j))
+                    .with_operations(operations)
+                    .with_gap(gap),
+            );
+        }
+    }
+    count_operations(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone(), 1.3).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_final_sort_large_shrink() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone(), 3.0).last();
+        let snapshot = result.unwrap();
+        assert!(test_util::is_sorted(&snapshot.items));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::sort::{
+    count_operations, key, AuxArray, CallStack, Complexity, Histogram, Info, Operation, Snapshot,
+    SortPointer,
+};
+use std::ops::Range;
+
+pub const INFO: Info = Info {
+    best: Complexity::LinearKeys,
+    average: Complexity::LinearKeys,
+    worst: Complexity::LinearKeys,
+    space: Complexity::LinearKeys,
+    stable: true,
+    in_place: false,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let min = input.iter().map(|x| key(*x)).min().unwrap_or(0);
+    let max = input.iter().map(|x| key(*x)).max().unwrap_or(0);
+    let mut output = AuxArray::new("Output", input.len());
+    let mut histogram = Histogram::new(format!("Counts ({}..={})", min, max), max - min + 1);
+    let len = input.len();
+    counting_pass(
+        input,
+        0..len,
+        &mut output,
+        &mut histogram,
+        |x| key(x) - min,
+        &CallStack::default(),
+        &mut result,
+    );
+    count_operations(result)
+}
+
+/// A stable counting pass over `range`: tallies each element's bucket, turns
+/// the tallies into positions, places the elements into `output` and copies
+/// them back. Afterwards `histogram` holds the offset of each bucket's first
+/// element within `range`.
+pub fn counting_pass(
+    input: &mut [f64],
+    range: Range<usize>,
+    output: &mut AuxArray,
+    histogram: &mut Histogram,
+    bucket_of: impl Fn(f64) -> usize,
+    stack: &CallStack,
+    result: &mut Vec<Snapshot>,
+) {
+    let snapshot = |input: &[f64],
+                    pointer: SortPointer,
+                    operations: Vec<Operation>,
+                    output: &AuxArray,
+                    histogram: &Histogram,
+                    phase: &'static str| {
+        Snapshot::new(input.to_vec(), pointer)
+            .with_operations(operations)
+            .with_aux(vec![output.clone()])
+            .with_histogram(histogram.clone())
+            .with_stack(stack.clone())
+            .with_phase(phase)
+    };
+
+    for i in range.clone() {
+        let bucket = bucket_of(input[i]);
+        histogram.counts[bucket] += 1;
+        histogram.current = Some(bucket);
+        result.push(snapshot(
+            input,
+            SortPointer(i, i),
+            vec![Operation::Distribute { index: i, bucket }],
+            output,
+            histogram,
+            "Count",
+        ));
+    }
+
+    for bucket in 1..histogram.counts.len() {
+        histogram.counts[bucket] += histogram.counts[bucket - 1];
+        histogram.current = Some(bucket);
+        result.push(snapshot(
+            input,
+            SortPointer::none(),
+            vec![],
+            output,
+            histogram,
+            "Prefix Sum",
+        ));
+    }
+
+    // Walk backwards so equal keys keep their order
+    for i in range.clone().rev() {
+        let bucket = bucket_of(input[i]);
+        histogram.counts[bucket] -= 1;
+        histogram.current = Some(bucket);
+        let position = range.start + histogram.counts[bucket];
+        output.set(position, input[i]);
+        result.push(snapshot(
+            input,
+            SortPointer(i, i),
+            vec![Operation::AuxWrite(position)],
+            output,
+            histogram,
+            "Place",
+        ));
+    }
+
+    output.write = None;
+    histogram.current = None;
+    for i in range {
+        input[i] = output.items[i].unwrap_or_default();
+        result.push(snapshot(
+            input,
+            SortPointer(i, i),
+            vec![Operation::Write(i)],
+            output,
+            histogram,
+            "Copy Back",
+        ));
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+}
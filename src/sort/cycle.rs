@@ -0,0 +1,80 @@
+use crate::sort::{count_operations, Complexity, Info, Operation, Snapshot, SortPointer};
+
+pub const INFO: Info = Info {
+    best: Complexity::Quadratic,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    for start in 0..input.len().saturating_sub(1) {
+        let mut item = input[start];
+        let mut position = find_position(input, start, item, &mut result);
+        if position == start {
+            continue;
+        }
+
+        // Rotate the rest of the cycle, writing each item straight into place
+        loop {
+            while item == input[position] {
+                position += 1;
+            }
+            std::mem::swap(&mut item, &mut input[position]);
+            result.push(
+                Snapshot::new(input.to_vec(), SortPointer(start, position))
+                    .with_operations(vec![Operation::Write(position)]),
+            );
+            if position == start {
+                break;
+            }
+            position = find_position(input, start, item, &mut result);
+        }
+    }
+    count_operations(result)
+}
+
+/// Counts the elements after `start` smaller than `item`, which gives the
+/// index `item` belongs at.
+fn find_position(input: &[f64], start: usize, item: f64, result: &mut Vec<Snapshot>) -> usize {
+    let mut position = start;
+    for i in start + 1..input.len() {
+        if input[i] < item {
+            position += 1;
+        }
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer(start, i))
+                .with_operations(vec![Operation::Compare(start, i)]),
+        );
+    }
+    position
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_minimum_writes() {
+        let mut items = vec![3.0, 1.0, 2.0, 5.0, 4.0, 4.0];
+        let result = create_iterator(&mut items).last();
+        let snapshot = result.unwrap();
+        assert_eq!(snapshot.items, vec![1.0, 2.0, 3.0, 4.0, 4.0, 5.0]);
+        assert_eq!(snapshot.counters.array_writes(), 5);
+    }
+}
@@ -0,0 +1,45 @@
+use crate::sort::{count_operations, Complexity, Info, Operation, Snapshot, SortPointer};
+
+pub const INFO: Info = Info {
+    best: Complexity::Linear,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Constant,
+    stable: true,
+    in_place: true,
+    adaptive: true,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let mut position = 1;
+    while position < input.len() {
+        let mut operations = vec![Operation::Compare(position - 1, position)];
+        let pointer = SortPointer(position - 1, position);
+        if input[position - 1] <= input[position] {
+            position += 1;
+        } else {
+            input.swap(position - 1, position);
+            operations.push(Operation::Swap(position - 1, position));
+            position = (position - 1).max(1);
+        }
+        result.push(Snapshot::new(input.to_vec(), pointer).with_operations(operations));
+    }
+    count_operations(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::sort::{count_operations, Complexity, Info, Operation, Snapshot, SortPointer};
+use std::ops::Range;
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::NLogN,
+    worst: Complexity::NLogN,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let len = input.len();
+    for root in (0..len / 2).rev() {
+        sift_down(
+            input,
+            0,
+            root,
+            len,
+            &|snapshot| snapshot.with_heap(len).with_phase("Heapify"),
+            &mut result,
+        );
+    }
+    for end in (1..len).rev() {
+        let extract = |snapshot: Snapshot| snapshot.with_heap(end).with_phase("Extract");
+        input.swap(0, end);
+        result.push(extract(
+            Snapshot::new(input.to_vec(), SortPointer(0, end))
+                .with_operations(vec![Operation::Swap(0, end)]),
+        ));
+        sift_down(input, 0, 0, end, &extract, &mut result);
+    }
+    count_operations(result)
+}
+
+/// Heap sorts `range`, for algorithms that fall back to heap sort on part of
+/// the array. `decorate` adds the caller's details to every snapshot.
+pub fn heap_sort_range(
+    input: &mut [f64],
+    range: Range<usize>,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) {
+    let (lo, len) = (range.start, range.len());
+    for root in (0..len / 2).rev() {
+        sift_down(input, lo, root, len, decorate, result);
+    }
+    for end in (1..len).rev() {
+        input.swap(lo, lo + end);
+        result.push(decorate(
+            Snapshot::new(input.to_vec(), SortPointer(lo, lo + end))
+                .with_operations(vec![Operation::Swap(lo, lo + end)]),
+        ));
+        sift_down(input, lo, 0, end, decorate, result);
+    }
+}
+
+/// Sifts `root` down the heap stored in `input[lo..lo + end]`, where `root`
+/// and `end` are relative to `lo`.
+fn sift_down(
+    input: &mut [f64],
+    lo: usize,
+    mut root: usize,
+    end: usize,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) {
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= end {
+            break;
+        }
+        let mut operations = vec![];
+        if child + 1 < end {
+            operations.push(Operation::Compare(lo + child, lo + child + 1));
+            if input[lo + child] < input[lo + child + 1] {
+                child += 1;
+            }
+        }
+        let (parent, child) = (lo + root, lo + child);
+        operations.push(Operation::Compare(parent, child));
+        let sifted = input[parent] < input[child];
+        if sifted {
+            input.swap(parent, child);
+            operations.push(Operation::Swap(parent, child));
+        }
+        result.push(decorate(
+            Snapshot::new(input.to_vec(), SortPointer(parent, child)).with_operations(operations),
+        ));
+        if !sifted {
+            break;
+        }
+        root = child - lo;
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::sort::{
+    count_operations, CallStack, Complexity, Info, Operation, Snapshot, SortPointer,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::NLogSquaredN,
+    worst: Complexity::NLogSquaredN,
+    space: Complexity::Log,
+    stable: true,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let mut stack = CallStack::default();
+    merge_sort(input, &mut stack, 0, input.len(), &mut result);
+    count_operations(result)
+}
+
+fn merge_sort(
+    input: &mut [f64],
+    stack: &mut CallStack,
+    lo: usize,
+    hi: usize,
+    result: &mut Vec<Snapshot>,
+) {
+    if hi - lo < 2 {
+        return;
+    }
+    stack.push(lo, hi);
+    let mid = lo + (hi - lo) / 2;
+    merge_sort(input, stack, lo, mid, result);
+    merge_sort(input, stack, mid, hi, result);
+    merge(input, stack, lo, mid, hi, result);
+    stack.pop();
+}
+
+/// Merges `lo..mid` and `mid..hi` without a buffer. The middle element of
+/// the longer run splits it, its place in the other run is found by binary
+/// search, and rotating the two inner pieces past each other leaves two
+/// smaller merges on either side of it.
+fn merge(
+    input: &mut [f64],
+    stack: &CallStack,
+    lo: usize,
+    mid: usize,
+    hi: usize,
+    result: &mut Vec<Snapshot>,
+) {
+    if lo == mid || mid == hi {
+        return;
+    }
+    if hi - lo == 2 {
+        let mut operations = vec![Operation::Compare(lo, mid)];
+        if input[mid] < input[lo] {
+            input.swap(lo, mid);
+            operations.push(Operation::Swap(lo, mid));
+        }
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer(lo, mid))
+                .with_operations(operations)
+                .with_stack(stack.clone())
+                .with_phase("Merge"),
+        );
+        return;
+    }
+
+    let (left_cut, right_cut) = if mid - lo >= hi - mid {
+        let left_cut = lo + (mid - lo) / 2;
+        let right_cut = search(input, stack, mid, hi, left_cut, false, result);
+        (left_cut, right_cut)
+    } else {
+        let right_cut = mid + (hi - mid) / 2;
+        let left_cut = search(input, stack, lo, mid, right_cut, true, result);
+        (left_cut, right_cut)
+    };
+
+    rotate(input, stack, left_cut, mid, right_cut, result);
+    let new_mid = left_cut + (right_cut - mid);
+    merge(input, stack, lo, left_cut, new_mid, result);
+    merge(input, stack, new_mid, right_cut, hi, result);
+}
+
+/// Finds where `input[key]` belongs in the sorted range `lo..hi`, after any
+/// equal elements when `after` is set so the merge stays stable.
+fn search(
+    input: &[f64],
+    stack: &CallStack,
+    mut lo: usize,
+    mut hi: usize,
+    key: usize,
+    after: bool,
+    result: &mut Vec<Snapshot>,
+) -> usize {
+    while lo < hi {
+        let probe = lo + (hi - lo) / 2;
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer(key, probe))
+                .with_operations(vec![Operation::Compare(key, probe)])
+                .with_stack(stack.clone())
+                .with_phase("Search"),
+        );
+        let goes_right = if after {
+            input[probe] <= input[key]
+        } else {
+            input[probe] < input[key]
+        };
+        if goes_right {
+            lo = probe + 1;
+        } else {
+            hi = probe;
+        }
+    }
+    lo
+}
+
+/// Swaps the blocks `lo..mid` and `mid..hi` with three reversals.
+fn rotate(
+    input: &mut [f64],
+    stack: &CallStack,
+    lo: usize,
+    mid: usize,
+    hi: usize,
+    result: &mut Vec<Snapshot>,
+) {
+    if lo == mid || mid == hi {
+        return;
+    }
+    for (start, end) in [(lo, mid), (mid, hi), (lo, hi)] {
+        if end - start < 2 {
+            continue;
+        }
+        input[start..end].reverse();
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer(start, end - 1))
+                .with_operations(vec![Operation::Reverse(start, end)])
+                .with_stack(stack.clone())
+                .with_phase("Rotate"),
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut items = vec![2.0, 1.0, 2.0, 1.0, 0.0, 2.0, 1.0, 0.0, 2.0];
+        create_iterator(&mut items).for_each(drop);
+        assert_eq!(items, vec![0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_no_aux_memory() {
+        let items = generate_random_data(100);
+        let snapshot = create_iterator(&mut items.clone()).last().unwrap();
+        assert_eq!(snapshot.counters.peak_aux_memory, 0);
+    }
+}
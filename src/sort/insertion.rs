@@ -1,92 +1,82 @@
-use crate::sort::{Sort, SortPointer};
+use crate::sort::{count_operations, Complexity, Info, Operation, Snapshot, SortPointer};
+use std::ops::Range;
 
-pub struct InsertionSort {
-    pub input: Vec<f64>,
-    pub items: Vec<f64>,
-    pub step: usize,
-    pub complete: bool,
-    pub active: bool,
-    pub iterator: Box<dyn Iterator<Item = (Vec<f64>, SortPointer)>>,
-    pub pointer: SortPointer,
-}
+pub const INFO: Info = Info {
+    best: Complexity::Linear,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Constant,
+    stable: true,
+    in_place: true,
+    adaptive: true,
+    online: true,
+};
 
-impl InsertionSort {
-    pub fn new(input: Vec<f64>) -> InsertionSort {
-        let items = input.clone();
-        let mut iterator_target = input.clone();
-        InsertionSort {
-            input,
-            items,
-            step: 0,
-            complete: false,
-            active: false,
-            iterator: create_iterator(&mut iterator_target),
-            pointer: SortPointer(0, 1),
-        }
-    }
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let len = input.len();
+    insertion_sort_range(input, 0..len, &|snapshot| snapshot, &mut result);
+    count_operations(result)
 }
 
-impl Sort for InsertionSort {
-    fn step(&mut self) -> (&Vec<f64>, &SortPointer) {
-        if let Some((data, pointer)) = self.iterator.next() {
-            self.items = data;
-            self.pointer = pointer;
-            self.step = self.step + 1;
-        } else {
-            self.complete = true;
-            self.deactivate_sort();
+/// Insertion sorts `range`, for algorithms that finish small runs of the
+/// array this way. `decorate` adds the caller's details to every snapshot.
+pub fn insertion_sort_range(
+    input: &mut [f64],
+    range: Range<usize>,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) {
+    for i in range.start + 1..range.end {
+        let mut j = i;
+        while j > range.start {
+            let mut operations = vec![Operation::Compare(j, j - 1)];
+            let shifted = input[j] < input[j - 1];
+            if shifted {
+                input.swap(j, j - 1);
+                operations.push(Operation::Swap(j, j - 1));
+                j -= 1;
+            }
+            result.push(decorate(
+                Snapshot::new(input.to_vec(), SortPointer(i, j)).with_operations(operations),
+            ));
+            if !shifted {
+                break;
+            }
         }
-
-        (&self.items, &self.pointer)
-    }
-    fn items(&self) -> &Vec<f64> {
-        &self.items
-    }
-    fn is_active(&self) -> bool {
-        self.active
-    }
-    fn is_sorted(&self) -> bool {
-        self.complete
-    }
-    fn toggle_sort(&mut self) {
-        self.active = !self.active;
-    }
-    fn activate_sort(&mut self) {
-        self.active = true;
-    }
-    fn deactivate_sort(&mut self) {
-        self.active = false;
-    }
-    fn get_pointer(&self) -> &SortPointer {
-        &self.pointer
-    }
-    fn get_name(&self) -> String {
-        "Insertion Sort".to_string()
-    }
-    fn get_current_step(&self) -> usize {
-        self.step
-    }
-    fn reset(&mut self, items: Vec<f64>) {
-        self.input = items.clone();
-        self.items = items.clone();
-        self.iterator = create_iterator(&mut self.input);
-        self.step = 0;
-        self.complete = false;
-        self.active = false;
     }
 }
 
-pub fn create_iterator(input: &mut Vec<f64>) -> Box<dyn Iterator<Item = (Vec<f64>, SortPointer)>> {
-    let mut result = vec![];
-    for i in 1..input.len() {
-        let mut j = i;
-        while j > 0 && input[j] < input[j - 1] {
-            input.swap(j, j - 1);
-            j = j - 1;
-            result.push((input.clone(), SortPointer(i, j)));
+/// Like `insertion_sort_range`, but finds each insertion point by binary
+/// search over the sorted prefix before shifting the element into place.
+pub fn binary_insertion_sort_range(
+    input: &mut [f64],
+    range: Range<usize>,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) {
+    for i in range.start + 1..range.end {
+        let (mut left, mut right) = (range.start, i);
+        while left < right {
+            let mid = (left + right) / 2;
+            result.push(decorate(
+                Snapshot::new(input.to_vec(), SortPointer(i, mid))
+                    .with_operations(vec![Operation::Compare(i, mid)]),
+            ));
+            if input[i] < input[mid] {
+                right = mid;
+            } else {
+                left = mid + 1;
+            }
+        }
+        for j in (left..i).rev() {
+            input.swap(j, j + 1);
+            result.push(decorate(
+                Snapshot::new(input.to_vec(), SortPointer(i, j))
+                    .with_operations(vec![Operation::Swap(j, j + 1)]),
+            ));
         }
     }
-    Box::new(result.into_iter())
 }
 
 #[cfg(test)]
@@ -98,8 +88,8 @@ pub mod tests {
     fn test_final_sort() {
         let items = generate_random_data(100);
         let result = create_iterator(&mut items.clone()).last();
-        let (data, _) = result.unwrap();
-        let sorted = test_util::is_sorted(&data);
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
         assert!(sorted);
     }
 }
@@ -0,0 +1,143 @@
+use crate::sort::{
+    count_operations, heap::heap_sort_range, insertion::insertion_sort_range,
+    quick::lomuto_partition, CallStack, Complexity, Info, Operation, Snapshot, SortPointer,
+};
+use std::ops::Range;
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::NLogN,
+    worst: Complexity::NLogN,
+    space: Complexity::Log,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let mut stack = CallStack::default();
+    let len = input.len();
+    let depth_limit = 2 * (usize::BITS - len.leading_zeros()) as usize;
+    intro_sort(input, &mut stack, 0..len, depth_limit, &mut result);
+    count_operations(result)
+}
+
+/// Ranges this short are finished with insertion sort, as in libstdc++.
+const INSERTION_THRESHOLD: usize = 16;
+
+fn intro_sort(
+    input: &mut [f64],
+    stack: &mut CallStack,
+    range: Range<usize>,
+    depth_limit: usize,
+    result: &mut Vec<Snapshot>,
+) {
+    if range.len() < 2 {
+        return;
+    }
+    stack.push(range.start, range.end);
+
+    if range.len() <= INSERTION_THRESHOLD {
+        insertion_sort_range(
+            input,
+            range,
+            &|snapshot| snapshot.with_stack(stack.clone()).with_phase("Insertion"),
+            result,
+        );
+    } else if depth_limit == 0 {
+        // Too many unbalanced partitions, so give up on quicksort here
+        heap_sort_range(
+            input,
+            range,
+            &|snapshot| {
+                snapshot
+                    .with_stack(stack.clone())
+                    .with_phase("Heapsort Fallback")
+            },
+            result,
+        );
+    } else {
+        let (lo, hi) = (range.start, range.end);
+        let decorate =
+            |snapshot: Snapshot| snapshot.with_stack(stack.clone()).with_phase("Quicksort");
+        let median = median_of_three(input, lo, lo + (hi - lo) / 2, hi - 1, &decorate, result);
+        if median != hi - 1 {
+            input.swap(median, hi - 1);
+            result.push(decorate(
+                Snapshot::new(input.to_vec(), SortPointer(median, hi - 1))
+                    .with_operations(vec![Operation::Swap(median, hi - 1)]),
+            ));
+        }
+        stack.set_pivot(hi - 1);
+        let pivot = lomuto_partition(
+            input,
+            lo,
+            hi,
+            &|snapshot| snapshot.with_stack(stack.clone()).with_phase("Quicksort"),
+            result,
+        );
+        stack.set_pivot(pivot);
+        intro_sort(input, stack, lo..pivot, depth_limit - 1, result);
+        intro_sort(input, stack, pivot + 1..hi, depth_limit - 1, result);
+    }
+    stack.pop();
+}
+
+/// Returns whichever of `a`, `b` and `c` holds the median value.
+pub fn median_of_three(
+    input: &[f64],
+    a: usize,
+    b: usize,
+    c: usize,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) -> usize {
+    let mut compare = |i: usize, j: usize| {
+        result.push(decorate(
+            Snapshot::new(input.to_vec(), SortPointer(i, j))
+                .with_operations(vec![Operation::Compare(i, j)]),
+        ));
+        input[i] < input[j]
+    };
+    if compare(a, b) {
+        if compare(b, c) {
+            b
+        } else if compare(a, c) {
+            c
+        } else {
+            a
+        }
+    } else if compare(a, c) {
+        a
+    } else if compare(b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_median_of_three() {
+        let items = [3.0, 1.0, 2.0];
+        for (a, b, c) in [(0, 1, 2), (1, 2, 0), (2, 0, 1), (2, 1, 0)] {
+            let median = median_of_three(&items, a, b, c, &|snapshot| snapshot, &mut vec![]);
+            assert_eq!(median, 2);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::sort::{
+    count_operations, AuxArray, CallStack, Complexity, Info, Operation, Snapshot, SortPointer,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::NLogN,
+    worst: Complexity::NLogN,
+    space: Complexity::Linear,
+    stable: true,
+    in_place: false,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let mut buffer = AuxArray::new("Buffer", input.len());
+    let mut stack = CallStack::default();
+    merge_sort(input, &mut buffer, &mut stack, 0, input.len(), &mut result);
+    count_operations(result)
+}
+
+fn merge_sort(
+    input: &mut [f64],
+    buffer: &mut AuxArray,
+    stack: &mut CallStack,
+    lo: usize,
+    hi: usize,
+    result: &mut Vec<Snapshot>,
+) {
+    if hi - lo < 2 {
+        return;
+    }
+    stack.push(lo, hi);
+    let mid = lo + (hi - lo) / 2;
+    merge_sort(input, buffer, stack, lo, mid, result);
+    merge_sort(input, buffer, stack, mid, hi, result);
+    merge(
+        input,
+        buffer,
+        lo,
+        mid,
+        hi,
+        &|snapshot| snapshot.with_stack(stack.clone()),
+        result,
+    );
+    stack.pop();
+}
+
+/// Merges the sorted runs `lo..mid` and `mid..hi` through `buffer`.
+/// `decorate` adds the caller's details to every snapshot.
+pub fn merge(
+    input: &mut [f64],
+    buffer: &mut AuxArray,
+    lo: usize,
+    mid: usize,
+    hi: usize,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) {
+    // Copy both halves into the buffer, then merge them back into place
+    for k in lo..hi {
+        buffer.set(k, input[k]);
+        result.push(decorate(
+            Snapshot::new(input.to_vec(), SortPointer(k, k))
+                .with_operations(vec![Operation::AuxWrite(k)])
+                .with_aux(vec![buffer.clone()]),
+        ));
+    }
+    buffer.write = None;
+
+    let (mut i, mut j) = (lo, mid);
+    for k in lo..hi {
+        let left = buffer.items[i.min(mid - 1)].unwrap_or_default();
+        let right = buffer.items[j.min(hi - 1)].unwrap_or_default();
+        let mut operations = vec![];
+        if i < mid && j < hi {
+            operations.push(Operation::Compare(i, j));
+        }
+        if j >= hi || (i < mid && left <= right) {
+            input[k] = left;
+            i += 1;
+        } else {
+            input[k] = right;
+            j += 1;
+        }
+        operations.push(Operation::Write(k));
+        result.push(decorate(
+            Snapshot::new(input.to_vec(), SortPointer(k, k))
+                .with_operations(operations)
+                .with_aux(vec![buffer.clone()]),
+        ));
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+}
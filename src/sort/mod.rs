@@ -1,36 +1,753 @@
+pub mod binary_insertion;
+pub mod bitonic;
+pub mod bogo;
+pub mod bottom_up_merge;
 pub mod bubble;
+pub mod bucket;
+pub mod circle;
+pub mod cocktail;
+pub mod comb;
+pub mod counting;
+pub mod cycle;
+pub mod gnome;
+pub mod heap;
+pub mod in_place_merge;
 pub mod insertion;
+pub mod intro;
+pub mod merge;
+pub mod network;
+pub mod observed;
+pub mod odd_even;
+pub mod odd_even_merge;
+pub mod pancake;
+pub mod patience;
+pub mod pdq;
+pub mod quick;
+pub mod radix_lsd;
+pub mod radix_msd;
 pub mod selection;
+pub mod shell;
+pub mod stooge;
+pub mod strand;
+pub mod tim;
+
+/// The playback state every sort shares: the input it was started on, and
+/// the precomputed snapshots stepped through one at a time. Snapshots taken
+/// from the iterator are kept in `history`, so playback can seek back.
+pub struct Stepper {
+    pub input: Vec<f64>,
+    pub step: usize,
+    pub complete: bool,
+    pub active: bool,
+    pub iterator: Box<dyn Iterator<Item = Snapshot>>,
+    pub snapshot: Snapshot,
+    pub history: Vec<Snapshot>,
+}
+
+impl Stepper {
+    pub fn new(input: Vec<f64>, iterator: Box<dyn Iterator<Item = Snapshot>>) -> Stepper {
+        Stepper {
+            snapshot: Snapshot::new(input.clone(), SortPointer(0, 1)),
+            input,
+            step: 0,
+            complete: false,
+            active: false,
+            iterator,
+            history: vec![],
+        }
+    }
+
+    pub fn step(&mut self) {
+        if let Some(snapshot) = self.history.get(self.step) {
+            self.snapshot = snapshot.clone();
+            self.step += 1;
+        } else if let Some(snapshot) = self.iterator.next() {
+            self.history.push(snapshot.clone());
+            self.snapshot = snapshot;
+            self.step += 1;
+        } else {
+            self.complete = true;
+            self.active = false;
+        }
+    }
+
+    /// The number of steps in the whole run, which takes every remaining
+    /// snapshot from the iterator.
+    pub fn total_steps(&mut self) -> usize {
+        self.history.extend(&mut self.iterator);
+        self.history.len()
+    }
+
+    /// Jumps to `step`, or the end of the run if it is shorter. Playback
+    /// carries on from there if it was running.
+    pub fn seek(&mut self, step: usize) {
+        let total = self.total_steps();
+        self.step = step.min(total);
+        self.snapshot = match self.step {
+            0 => Snapshot::new(self.input.clone(), SortPointer(0, 1)),
+            step => self.history[step - 1].clone(),
+        };
+        self.complete = self.step == total;
+        self.active &= !self.complete;
+    }
+
+    /// Starts over on `input`, paused, with the snapshots of sorting it.
+    pub fn restart(&mut self, input: Vec<f64>, iterator: Box<dyn Iterator<Item = Snapshot>>) {
+        *self = Stepper::new(input, iterator);
+    }
+}
+
+/// Builds the snapshots of sorting `input`, leaving it sorted.
+pub type Build = fn(&mut [f64]) -> Box<dyn Iterator<Item = Snapshot>>;
 
 pub trait Sort {
-    fn items(&self) -> &Vec<f64>;
-    fn step(&mut self) -> (&Vec<f64>, &SortPointer);
-    fn is_active(&self) -> bool;
-    fn is_sorted(&self) -> bool;
-    fn toggle_sort(&mut self);
-    fn activate_sort(&mut self);
-    fn deactivate_sort(&mut self);
-    fn get_pointer(&self) -> &SortPointer;
+    fn stepper(&self) -> &Stepper;
+    fn stepper_mut(&mut self) -> &mut Stepper;
     fn get_name(&self) -> String;
-    fn get_current_step(&self) -> usize;
-    fn reset(&mut self, items: Vec<f64>);
+    fn info(&self) -> &'static Info;
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>>;
+    fn items(&self) -> &Vec<f64> {
+        &self.stepper().snapshot.items
+    }
+    fn step(&mut self) -> (&Vec<f64>, &SortPointer) {
+        self.stepper_mut().step();
+        let snapshot = &self.stepper().snapshot;
+        (&snapshot.items, &snapshot.pointer)
+    }
+    fn is_active(&self) -> bool {
+        self.stepper().active
+    }
+    fn is_sorted(&self) -> bool {
+        self.stepper().complete
+    }
+    fn toggle_sort(&mut self) {
+        let stepper = self.stepper_mut();
+        stepper.active = !stepper.active;
+    }
+    fn activate_sort(&mut self) {
+        self.stepper_mut().active = true;
+    }
+    fn deactivate_sort(&mut self) {
+        self.stepper_mut().active = false;
+    }
+    fn get_pointer(&self) -> &SortPointer {
+        &self.stepper().snapshot.pointer
+    }
+    fn get_current_step(&self) -> usize {
+        self.stepper().step
+    }
+    fn total_steps(&mut self) -> usize {
+        self.stepper_mut().total_steps()
+    }
+    fn seek(&mut self, step: usize) {
+        self.stepper_mut().seek(step);
+    }
+    fn reset(&mut self, items: Vec<f64>) {
+        let iterator = self.create_iterator(&mut items.clone());
+        self.stepper_mut().restart(items, iterator);
+    }
+    fn get_snapshot(&self) -> &Snapshot {
+        &self.stepper().snapshot
+    }
+    fn aux_arrays(&self) -> &Vec<AuxArray> {
+        &self.get_snapshot().aux
+    }
+    fn call_stack(&self) -> &CallStack {
+        &self.get_snapshot().stack
+    }
+    fn is_recursive(&self) -> bool {
+        false
+    }
+    fn heap_size(&self) -> Option<usize> {
+        self.get_snapshot().heap_size
+    }
+    fn phase(&self) -> Option<&'static str> {
+        self.get_snapshot().phase
+    }
+    fn counters(&self) -> &Counters {
+        &self.get_snapshot().counters
+    }
+    fn histogram(&self) -> Option<&Histogram> {
+        self.get_snapshot().histogram.as_ref()
+    }
+    fn gap(&self) -> Option<usize> {
+        self.get_snapshot().gap
+    }
+    fn comparators(&self) -> &Vec<(usize, usize)> {
+        &self.get_snapshot().comparators
+    }
+    fn network(&self) -> Option<&Network> {
+        None
+    }
+    fn variants(&self) -> &[Variant] {
+        &[]
+    }
+    fn piles(&self) -> Option<&Piles> {
+        self.get_snapshot().piles.as_ref()
+    }
+    fn pivots(&self) -> &Vec<usize> {
+        &self.get_snapshot().pivots
+    }
+    fn boundaries(&self) -> &Vec<usize> {
+        &self.get_snapshot().boundaries
+    }
+    fn settings(&self) -> Vec<Setting> {
+        vec![]
+    }
+    /// A problem that stopped the sort from running to the end, such as a
+    /// failing script.
+    fn error(&self) -> Option<String> {
+        None
+    }
+    /// Moves the setting called `name` on to its next value and restarts the
+    /// sort on the same input.
+    fn cycle_setting(&mut self, _name: &str) {}
+}
+
+/// A sort with no options of its own, fully described by its name,
+/// properties and snapshot generator.
+pub struct BasicSort {
+    pub stepper: Stepper,
+    pub name: &'static str,
+    pub info: &'static Info,
+    pub recursive: bool,
+    pub build: Build,
+}
+
+impl BasicSort {
+    pub fn new(
+        input: Vec<f64>,
+        name: &'static str,
+        info: &'static Info,
+        recursive: bool,
+        build: Build,
+    ) -> BasicSort {
+        let iterator = build(&mut input.clone());
+        BasicSort {
+            stepper: Stepper::new(input, iterator),
+            name,
+            info,
+            recursive,
+            build,
+        }
+    }
+}
+
+impl Sort for BasicSort {
+    fn stepper(&self) -> &Stepper {
+        &self.stepper
+    }
+    fn stepper_mut(&mut self) -> &mut Stepper {
+        &mut self.stepper
+    }
+    fn get_name(&self) -> String {
+        self.name.to_string()
+    }
+    fn info(&self) -> &'static Info {
+        self.info
+    }
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+        (self.build)(input)
+    }
+    fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+}
+
+/// An option of a sort that can be switched from the sort view, with the
+/// name of its current value. The key that switches it is looked up by `name`.
+pub struct Setting {
+    pub name: &'static str,
+    pub value: &'static str,
+}
+
+/// A growth rate in the input size n, ordered from slowest to fastest
+/// growing. `k` is the range of keys and `d` their number of digits.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Complexity {
+    Constant,
+    Log,
+    Linear,
+    LinearKeys,
+    Digits,
+    NLogN,
+    NLogSquaredN,
+    FourThirds,
+    ThreeHalves,
+    Quadratic,
+    Stooge,
+    Factorial,
+    Unbounded,
+    Unknown,
+}
+
+impl Complexity {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Log => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::LinearKeys => "O(n + k)",
+            Complexity::Digits => "O(d(n + k))",
+            Complexity::NLogN => "O(n log n)",
+            Complexity::NLogSquaredN => "O(n log² n)",
+            Complexity::FourThirds => "O(n^4/3)",
+            Complexity::ThreeHalves => "O(n^3/2)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Stooge => "O(n^2.71)",
+            Complexity::Factorial => "O(n·n!)",
+            Complexity::Unbounded => "unbounded",
+            Complexity::Unknown => "unknown",
+        }
+    }
+}
+
+/// Textbook properties of an algorithm, shown in the menu's info pane.
+/// `space` is the auxiliary space on top of the input.
+pub struct Info {
+    pub best: Complexity,
+    pub average: Complexity,
+    pub worst: Complexity,
+    pub space: Complexity,
+    pub stable: bool,
+    pub in_place: bool,
+    pub adaptive: bool,
+    pub online: bool,
+}
+
+/// A yes/no property of an algorithm that the menu can filter by.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Property {
+    Stable,
+    InPlace,
+    Adaptive,
+    Online,
 }
 
+impl Property {
+    pub const ALL: [Property; 4] = [
+        Property::Stable,
+        Property::InPlace,
+        Property::Adaptive,
+        Property::Online,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Property::Stable => "stable",
+            Property::InPlace => "in-place",
+            Property::Adaptive => "adaptive",
+            Property::Online => "online",
+        }
+    }
+
+    pub fn holds(&self, info: &Info) -> bool {
+        match self {
+            Property::Stable => info.stable,
+            Property::InPlace => info.in_place,
+            Property::Adaptive => info.adaptive,
+            Property::Online => info.online,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct SortPointer(pub usize, pub usize);
 
+impl SortPointer {
+    /// A pointer that highlights nothing, for steps that touch no element.
+    pub fn none() -> SortPointer {
+        SortPointer(usize::MAX, usize::MAX)
+    }
+}
+
+/// A named scratch buffer used by out-of-place algorithms. Empty slots are
+/// `None`, `write` marks the slot written during the current step and `read`
+/// the one compared against.
+#[derive(Clone)]
+pub struct AuxArray {
+    pub name: &'static str,
+    pub items: Vec<Option<f64>>,
+    pub write: Option<usize>,
+    pub read: Option<usize>,
+}
+
+impl AuxArray {
+    pub fn new(name: &'static str, len: usize) -> AuxArray {
+        AuxArray {
+            name,
+            items: vec![None; len],
+            write: None,
+            read: None,
+        }
+    }
+    pub fn set(&mut self, index: usize, value: f64) {
+        self.items[index] = Some(value);
+        self.write = Some(index);
+    }
+}
+
+/// A primitive operation performed during a step. `Distribute` counts or
+/// places the element at `index` into `bucket` without comparing it, and
+/// `Reverse` flips the range `lo..hi` in one go.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Operation {
+    Compare(usize, usize),
+    Swap(usize, usize),
+    Write(usize),
+    Read(usize),
+    AuxWrite(usize),
+    Distribute { index: usize, bucket: usize },
+    Reverse(usize, usize),
+}
+
+/// Running totals of the operations performed so far, along with the extra
+/// space in use, in elements, and the most that has been in use at once.
+#[derive(Clone, Copy, Default)]
+pub struct Counters {
+    pub comparisons: usize,
+    pub swaps: usize,
+    pub writes: usize,
+    pub aux_writes: usize,
+    pub reversals: usize,
+    pub reads: usize,
+    pub aux_memory: usize,
+    pub peak_aux_memory: usize,
+}
+
+impl Counters {
+    /// Writes into the main array, counting both halves of every swap.
+    pub fn array_writes(&self) -> usize {
+        self.swaps * 2 + self.writes
+    }
+    pub fn record(&mut self, operation: &Operation) {
+        match operation {
+            Operation::Compare(..) => self.comparisons += 1,
+            Operation::Swap(..) => self.swaps += 1,
+            Operation::Write(..) => self.writes += 1,
+            Operation::Read(..) => self.reads += 1,
+            Operation::AuxWrite(..) | Operation::Distribute { .. } => self.aux_writes += 1,
+            // A reversal moves elements like the swaps it is made of
+            Operation::Reverse(lo, hi) => {
+                self.reversals += 1;
+                self.swaps += (hi - lo) / 2;
+            }
+        }
+    }
+}
+
+/// The operation totals of one variant of a sort over the same input, for
+/// sorts whose variants can be compared side by side.
+#[derive(Clone)]
+pub struct Variant {
+    pub name: &'static str,
+    pub counters: Counters,
+    pub current: bool,
+}
+
+/// Per-bucket counts for distribution sorts, with `current` marking the
+/// bucket touched during the step.
+#[derive(Clone)]
+pub struct Histogram {
+    pub name: String,
+    pub counts: Vec<usize>,
+    pub current: Option<usize>,
+}
+
+impl Histogram {
+    pub fn new(name: String, buckets: usize) -> Histogram {
+        Histogram {
+            name,
+            counts: vec![0; buckets],
+            current: None,
+        }
+    }
+}
+
+/// A sorting network as a list of layers, each a set of compare-exchanges on
+/// distinct elements that could all run at the same time. Every comparator
+/// `(i, j)` has `i < j` and leaves the smaller element at `i`.
+pub type Network = Vec<Vec<(usize, usize)>>;
+
+/// The piles of a patience sort, bottom card first, with `current` marking
+/// the pile touched during the step.
+#[derive(Clone)]
+pub struct Piles {
+    pub piles: Vec<Vec<f64>>,
+    pub current: Option<usize>,
+}
+
+/// An active call of a divide-and-conquer sort, covering `lo..hi`.
+#[derive(Clone)]
+pub struct RecursionFrame {
+    pub lo: usize,
+    pub hi: usize,
+    pub pivot: Option<usize>,
+    pub depth: usize,
+}
+
+/// The recursion frames active at a step, innermost last, along with the
+/// deepest the stack has been so far.
+#[derive(Clone)]
+pub struct CallStack {
+    pub name: &'static str,
+    pub frames: Vec<RecursionFrame>,
+    pub peak_depth: usize,
+}
+
+impl Default for CallStack {
+    fn default() -> Self {
+        CallStack::named("Call Stack")
+    }
+}
+
+impl CallStack {
+    /// A stack shown under a title other than "Call Stack", for sorts whose
+    /// frames are not recursive calls.
+    pub fn named(name: &'static str) -> CallStack {
+        CallStack {
+            name,
+            frames: vec![],
+            peak_depth: 0,
+        }
+    }
+    pub fn push(&mut self, lo: usize, hi: usize) {
+        let depth = self.frames.len() + 1;
+        self.frames.push(RecursionFrame {
+            lo,
+            hi,
+            pivot: None,
+            depth,
+        });
+        self.peak_depth = self.peak_depth.max(depth);
+    }
+    pub fn pop(&mut self) {
+        self.frames.pop();
+    }
+    pub fn set_pivot(&mut self, pivot: usize) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.pivot = Some(pivot);
+        }
+    }
+    pub fn current(&self) -> Option<&RecursionFrame> {
+        self.frames.last()
+    }
+}
+
+/// The state of a sort after a single step. `heap_size` is set by sorts that
+/// keep a binary heap in `items[..heap_size]`, and `phase` names the stage of
+/// the algorithm the step belongs to. `gap` is the distance between compared
+/// elements for gapped sorts, and `comparators` lists every pair compared at
+/// once by sorts that work in parallel layers. `pivots` holds the indices
+/// of the pivots in use, and `boundaries` the indices where the regions of
+/// a partition in progress begin. `aux_memory` is the extra
+/// space in use for sorts that hold elements outside `aux`, whose combined
+/// length counts otherwise. `counters` is filled in from the operations of
+/// every step so far by `count_operations`.
+#[derive(Clone)]
+pub struct Snapshot {
+    pub items: Vec<f64>,
+    pub pointer: SortPointer,
+    pub operations: Vec<Operation>,
+    pub counters: Counters,
+    pub aux: Vec<AuxArray>,
+    pub stack: CallStack,
+    pub heap_size: Option<usize>,
+    pub phase: Option<&'static str>,
+    pub gap: Option<usize>,
+    pub comparators: Vec<(usize, usize)>,
+    pub histogram: Option<Histogram>,
+    pub piles: Option<Piles>,
+    pub aux_memory: Option<usize>,
+    pub pivots: Vec<usize>,
+    pub boundaries: Vec<usize>,
+}
+
+impl Snapshot {
+    pub fn new(items: Vec<f64>, pointer: SortPointer) -> Snapshot {
+        Snapshot {
+            items,
+            pointer,
+            operations: vec![],
+            counters: Counters::default(),
+            aux: vec![],
+            stack: CallStack::default(),
+            heap_size: None,
+            phase: None,
+            gap: None,
+            comparators: vec![],
+            histogram: None,
+            piles: None,
+            aux_memory: None,
+            pivots: vec![],
+            boundaries: vec![],
+        }
+    }
+    pub fn with_operations(mut self, operations: Vec<Operation>) -> Snapshot {
+        self.operations = operations;
+        self
+    }
+    pub fn with_aux(mut self, aux: Vec<AuxArray>) -> Snapshot {
+        self.aux = aux;
+        self
+    }
+    pub fn with_stack(mut self, stack: CallStack) -> Snapshot {
+        self.stack = stack;
+        self
+    }
+    pub fn with_heap(mut self, heap_size: usize) -> Snapshot {
+        self.heap_size = Some(heap_size);
+        self
+    }
+    pub fn with_phase(mut self, phase: &'static str) -> Snapshot {
+        self.phase = Some(phase);
+        self
+    }
+    pub fn with_gap(mut self, gap: usize) -> Snapshot {
+        self.gap = Some(gap);
+        self
+    }
+    pub fn with_comparators(mut self, comparators: Vec<(usize, usize)>) -> Snapshot {
+        self.comparators = comparators;
+        self
+    }
+    pub fn with_piles(mut self, piles: Piles) -> Snapshot {
+        self.piles = Some(piles);
+        self
+    }
+    pub fn with_aux_memory(mut self, aux_memory: usize) -> Snapshot {
+        self.aux_memory = Some(aux_memory);
+        self
+    }
+    pub fn with_pivots(mut self, pivots: Vec<usize>) -> Snapshot {
+        self.pivots = pivots;
+        self
+    }
+    pub fn with_boundaries(mut self, boundaries: Vec<usize>) -> Snapshot {
+        self.boundaries = boundaries;
+        self
+    }
+    pub fn with_histogram(mut self, histogram: Histogram) -> Snapshot {
+        self.histogram = Some(histogram);
+        self
+    }
+}
+
+/// Turns the snapshots built by a sort into its step iterator, keeping a
+/// running count of the operations each step performs.
+pub fn count_operations<I>(snapshots: I) -> Box<dyn Iterator<Item = Snapshot>>
+where
+    I: IntoIterator<Item = Snapshot>,
+    I::IntoIter: 'static,
+{
+    let mut counters = Counters::default();
+    Box::new(snapshots.into_iter().map(move |mut snapshot| {
+        for operation in &snapshot.operations {
+            counters.record(operation);
+        }
+        counters.aux_memory = snapshot
+            .aux_memory
+            .unwrap_or_else(|| snapshot.aux.iter().map(|aux| aux.items.len()).sum());
+        counters.peak_aux_memory = counters.peak_aux_memory.max(counters.aux_memory);
+        snapshot.counters = counters;
+        snapshot
+    }))
+}
+
+/// The integer key used by distribution sorts.
+pub fn key(value: f64) -> usize {
+    value.max(0.0) as usize
+}
+
+/// Generates whole numbers in `0..100`, so the values double as integer keys.
 pub fn generate_random_data(size: usize) -> Vec<f64> {
     use rand::{distributions::Standard, Rng};
 
     rand::thread_rng()
         .sample_iter::<f64, Standard>(Standard)
         .take(size)
-        .map(|x| x * 100.0)
+        .map(|x| (x * 100.0).floor())
         .collect()
 }
 
+/// Returns, for every element of `items`, the index it will occupy once sorted.
+/// Equal values keep their relative order.
+pub fn final_positions(items: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by(|&a, &b| items[a].total_cmp(&items[b]));
+
+    let mut positions = vec![0; items.len()];
+    for (position, index) in order.into_iter().enumerate() {
+        positions[index] = position;
+    }
+    positions
+}
+
+/// Returns the index in `input` that the element at `index` of `items`
+/// started from. Equal values are matched up in the order they appear.
+pub fn original_index(input: &[f64], items: &[f64], index: usize) -> Option<usize> {
+    let value = *items.get(index)?;
+    let earlier = items[..index].iter().filter(|x| **x == value).count();
+    input
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| **x == value)
+        .nth(earlier)
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_final_positions() {
+        let positions = final_positions(&[30.0, 10.0, 20.0, 10.0]);
+        assert_eq!(positions, vec![3, 0, 2, 1]);
+    }
+
+    #[test]
+    fn test_original_index() {
+        let input = [30.0, 10.0, 20.0, 10.0];
+        let items = [10.0, 10.0, 20.0, 30.0];
+        assert_eq!(original_index(&input, &items, 0), Some(1));
+        assert_eq!(original_index(&input, &items, 1), Some(3));
+        assert_eq!(original_index(&input, &items, 3), Some(0));
+        assert_eq!(original_index(&input, &items, 4), None);
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut sort = BasicSort::new(
+            vec![3.0, 2.0, 1.0],
+            "Bubble Sort",
+            &bubble::INFO,
+            false,
+            bubble::create_iterator,
+        );
+        sort.step();
+        let first = sort.items().clone();
+        let total = sort.total_steps();
+        assert!(total > 1);
+        assert_eq!(sort.get_current_step(), 1);
+
+        sort.seek(total + 5);
+        assert!(sort.is_sorted());
+        assert_eq!(sort.get_current_step(), total);
+        assert!(test_util::is_sorted(sort.items()));
+
+        // Seeking back replays the same snapshots
+        sort.seek(0);
+        assert!(!sort.is_sorted());
+        assert_eq!(*sort.items(), vec![3.0, 2.0, 1.0]);
+        sort.step();
+        assert_eq!(*sort.items(), first);
+    }
+}
+
 #[cfg(test)]
 pub mod test_util {
-    pub fn is_sorted(data: &Vec<f64>) -> bool {
+    pub fn is_sorted(data: &[f64]) -> bool {
         data.windows(2).all(|w| w[0] <= w[1])
     }
 }
@@ -0,0 +1,38 @@
+use crate::sort::{Network, Operation, Snapshot, SortPointer};
+
+/// Runs `network` over `input`, one snapshot per layer.
+pub fn apply_network(input: &mut [f64], network: &Network) -> Vec<Snapshot> {
+    let mut result = vec![];
+    for layer in network {
+        let mut operations = vec![];
+        for &(i, j) in layer {
+            operations.push(Operation::Compare(i, j));
+            if input[j] < input[i] {
+                input.swap(i, j);
+                operations.push(Operation::Swap(i, j));
+            }
+        }
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer::none())
+                .with_operations(operations)
+                .with_comparators(layer.clone()),
+        );
+    }
+    result
+}
+
+/// Drops the comparators touching elements past `len`. Networks built for
+/// the next power of two still sort `len` elements this way, as if the
+/// missing ones were infinitely large and never moved.
+pub fn truncate_network(network: Network, len: usize) -> Network {
+    network
+        .into_iter()
+        .map(|layer| {
+            layer
+                .into_iter()
+                .filter(|&(_, j)| j < len)
+                .collect::<Vec<_>>()
+        })
+        .filter(|layer| !layer.is_empty())
+        .collect()
+}
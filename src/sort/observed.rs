@@ -0,0 +1,276 @@
+use crate::sort::{count_operations, Info, Operation, Snapshot, Sort, SortPointer, Stepper};
+use std::{
+    cmp::Ordering,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering as AtomicOrdering},
+        mpsc::{self, SyncSender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// An array that reports every operation performed on it, so a sort can be
+/// written as ordinary Rust and still be stepped through. Each `compare`,
+/// `swap`, `read` and `write` becomes one snapshot.
+pub struct ObservedArray {
+    items: Vec<f64>,
+    /// Where snapshots go, until nobody is watching any more.
+    sender: SyncSender<Snapshot>,
+    /// Set once the run is no longer wanted.
+    cancel: Arc<AtomicBool>,
+}
+
+/// The payload a procedure is unwound with once its run is cancelled, so it
+/// stops at its next operation rather than finishing unobserved.
+struct Cancelled;
+
+impl ObservedArray {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    pub fn compare(&mut self, i: usize, j: usize) -> Ordering {
+        self.emit(SortPointer(i, j), Operation::Compare(i, j));
+        self.items[i].total_cmp(&self.items[j])
+    }
+    pub fn swap(&mut self, i: usize, j: usize) {
+        self.items.swap(i, j);
+        self.emit(SortPointer(i, j), Operation::Swap(i, j));
+    }
+    pub fn read(&mut self, index: usize) -> f64 {
+        self.emit(SortPointer(index, index), Operation::Read(index));
+        self.items[index]
+    }
+    pub fn write(&mut self, index: usize, value: f64) {
+        self.items[index] = value;
+        self.emit(SortPointer(index, index), Operation::Write(index));
+    }
+
+    fn emit(&mut self, pointer: SortPointer, operation: Operation) {
+        let snapshot = Snapshot::new(self.items.clone(), pointer).with_operations(vec![operation]);
+        // Once the run is cancelled or its steps are dropped, nobody is
+        // watching and the procedure is stopped
+        if self.cancel.load(AtomicOrdering::Relaxed) || self.sender.send(snapshot).is_err() {
+            panic::resume_unwind(Box::new(Cancelled));
+        }
+    }
+}
+
+/// A sort written against `ObservedArray`.
+pub type Procedure = fn(&mut ObservedArray);
+
+/// One run of a procedure: where it reports a panic, and the flag that
+/// stops it. Every run has its own, so a run that is being stopped cannot
+/// report into the one that replaced it.
+#[derive(Clone, Default)]
+pub struct Run {
+    pub error: Arc<Mutex<Option<String>>>,
+    pub cancel: Arc<AtomicBool>,
+}
+
+impl Run {
+    /// Stops the procedure at its next operation.
+    pub fn cancel(&self) {
+        self.cancel.store(true, AtomicOrdering::Relaxed);
+    }
+}
+
+/// Runs `procedure` on `input` on its own thread, which waits after every
+/// operation until its snapshot is taken. The input itself is left as it
+/// was. If the procedure panics the steps end there and the message is put
+/// in the run's `error`.
+pub fn observe(
+    procedure: Procedure,
+    input: &[f64],
+    run: Run,
+) -> Box<dyn Iterator<Item = Snapshot>> {
+    let (sender, receiver) = mpsc::sync_channel(0);
+    let mut array = ObservedArray {
+        items: input.to_vec(),
+        sender,
+        cancel: run.cancel,
+    };
+    let error = run.error;
+    thread::spawn(move || {
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| procedure(&mut array))) {
+            if payload.is::<Cancelled>() {
+                return;
+            }
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "the sort panicked".to_string());
+            *error.lock().unwrap() = Some(message);
+        }
+    });
+    count_operations(receiver)
+}
+
+/// A steppable sort made from a `Procedure`.
+pub struct ObservedSort {
+    pub stepper: Stepper,
+    pub name: &'static str,
+    pub info: &'static Info,
+    pub procedure: Procedure,
+    /// The run being stepped through.
+    pub run: Run,
+}
+
+impl ObservedSort {
+    pub fn new(
+        input: Vec<f64>,
+        name: &'static str,
+        info: &'static Info,
+        procedure: Procedure,
+    ) -> ObservedSort {
+        let run = Run::default();
+        let iterator = observe(procedure, &input, run.clone());
+        ObservedSort {
+            stepper: Stepper::new(input, iterator),
+            name,
+            info,
+            procedure,
+            run,
+        }
+    }
+}
+
+impl Sort for ObservedSort {
+    fn stepper(&self) -> &Stepper {
+        &self.stepper
+    }
+    fn stepper_mut(&mut self) -> &mut Stepper {
+        &mut self.stepper
+    }
+    fn get_name(&self) -> String {
+        self.name.to_string()
+    }
+    fn info(&self) -> &'static Info {
+        self.info
+    }
+    /// Steps of a run apart from the one shown, whose errors go unreported.
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+        observe(self.procedure, input, Run::default())
+    }
+    /// Stops the current run and starts a new one with an error slot of its
+    /// own.
+    fn reset(&mut self, items: Vec<f64>) {
+        self.run.cancel();
+        self.run = Run::default();
+        let iterator = observe(self.procedure, &items, self.run.clone());
+        self.stepper.restart(items, iterator);
+    }
+    fn error(&self) -> Option<String> {
+        self.run.error.lock().unwrap().clone()
+    }
+}
+
+impl Drop for ObservedSort {
+    fn drop(&mut self) {
+        self.run.cancel();
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::Complexity;
+
+    const INFO: Info = Info {
+        best: Complexity::Quadratic,
+        average: Complexity::Quadratic,
+        worst: Complexity::Quadratic,
+        space: Complexity::Constant,
+        stable: true,
+        in_place: true,
+        adaptive: false,
+        online: false,
+    };
+
+    fn insertion_sort(array: &mut ObservedArray) {
+        for i in 1..array.len() {
+            let value = array.read(i);
+            let mut j = i;
+            while j > 0 && array.read(j - 1) > value {
+                let previous = array.read(j - 1);
+                array.write(j, previous);
+                j -= 1;
+            }
+            array.write(j, value);
+        }
+    }
+
+    #[test]
+    fn test_steps() {
+        let mut sort = ObservedSort::new(vec![2.0, 1.0], "Insertion", &INFO, insertion_sort);
+        let mut operations = vec![];
+        loop {
+            sort.step();
+            if sort.is_sorted() {
+                break;
+            }
+            operations.extend(sort.get_snapshot().operations.clone());
+        }
+        assert_eq!(
+            operations,
+            vec![
+                Operation::Read(1),
+                Operation::Read(0),
+                Operation::Read(0),
+                Operation::Write(1),
+                Operation::Write(0),
+            ]
+        );
+        assert_eq!(sort.items(), &vec![1.0, 2.0]);
+        assert_eq!(sort.counters().reads, 3);
+    }
+
+    #[test]
+    fn test_reset_mid_run() {
+        let mut sort = ObservedSort::new(vec![3.0, 2.0, 1.0], "Insertion", &INFO, insertion_sort);
+        sort.step();
+        sort.reset(vec![2.0, 1.0]);
+        while !sort.is_sorted() {
+            sort.step();
+        }
+        assert_eq!(sort.items(), &vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_reset_drops_stale_error() {
+        // Panics on three items, after the first operation
+        fn broken_on_three(array: &mut ObservedArray) {
+            array.read(0);
+            assert!(array.len() != 3, "three items");
+        }
+        let mut sort = ObservedSort::new(vec![3.0, 2.0, 1.0], "Broken", &INFO, broken_on_three);
+        let old = sort.run.clone();
+        sort.reset(vec![2.0, 1.0]);
+        while !sort.is_sorted() {
+            sort.step();
+        }
+        // The cancelled run stops at its next operation instead of panicking
+        while Arc::strong_count(&old.error) > 1 {
+            thread::yield_now();
+        }
+        assert_eq!(*old.error.lock().unwrap(), None);
+        assert_eq!(sort.error(), None);
+    }
+
+    #[test]
+    fn test_panic_is_reported() {
+        let mut sort = ObservedSort::new(vec![2.0, 1.0], "Broken", &INFO, |array| {
+            array.swap(0, 5);
+        });
+        while !sort.is_sorted() {
+            sort.step();
+        }
+        assert!(sort
+            .error()
+            .is_some_and(|error| error.contains("out of bounds")));
+    }
+}
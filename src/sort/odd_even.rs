@@ -0,0 +1,51 @@
+use crate::sort::{count_operations, Complexity, Info, Operation, Snapshot, SortPointer};
+
+pub const INFO: Info = Info {
+    best: Complexity::Linear,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Constant,
+    stable: true,
+    in_place: true,
+    adaptive: true,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let mut sorted = false;
+    while !sorted {
+        sorted = true;
+        for (start, phase) in [(1, "Odd"), (0, "Even")] {
+            for i in (start..input.len().saturating_sub(1)).step_by(2) {
+                let mut operations = vec![Operation::Compare(i, i + 1)];
+                if input[i] > input[i + 1] {
+                    input.swap(i, i + 1);
+                    operations.push(Operation::Swap(i, i + 1));
+                    sorted = false;
+                }
+                result.push(
+                    Snapshot::new(input.to_vec(), SortPointer(i, i + 1))
+                        .with_operations(operations)
+                        .with_phase(phase),
+                );
+            }
+        }
+    }
+    count_operations(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::sort::{
+    count_operations,
+    network::{apply_network, truncate_network},
+    Complexity, Info, Network, Snapshot, Sort, Stepper,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogSquaredN,
+    average: Complexity::NLogSquaredN,
+    worst: Complexity::NLogSquaredN,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub struct OddEvenMergeSort {
+    pub stepper: Stepper,
+    pub network: Network,
+}
+
+impl OddEvenMergeSort {
+    pub fn new(input: Vec<f64>) -> OddEvenMergeSort {
+        let iterator = create_iterator(&mut input.clone());
+        OddEvenMergeSort {
+            network: network(input.len()),
+            stepper: Stepper::new(input, iterator),
+        }
+    }
+}
+
+impl Sort for OddEvenMergeSort {
+    fn stepper(&self) -> &Stepper {
+        &self.stepper
+    }
+    fn stepper_mut(&mut self) -> &mut Stepper {
+        &mut self.stepper
+    }
+    fn get_name(&self) -> String {
+        "Odd-Even Merge Sort".to_string()
+    }
+    fn info(&self) -> &'static Info {
+        &INFO
+    }
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+        create_iterator(input)
+    }
+    fn reset(&mut self, items: Vec<f64>) {
+        self.network = network(items.len());
+        let iterator = self.create_iterator(&mut items.clone());
+        self.stepper.restart(items, iterator);
+    }
+    fn network(&self) -> Option<&Network> {
+        Some(&self.network)
+    }
+}
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let network = network(input.len());
+    count_operations(apply_network(input, &network))
+}
+
+/// Builds Batcher's odd-even merge sorting network for `len` elements.
+pub fn network(len: usize) -> Network {
+    let size = len.next_power_of_two();
+    let mut network = vec![];
+    let mut p = 1;
+    while p < size {
+        let mut k = p;
+        while k >= 1 {
+            let mut layer = vec![];
+            for j in (k % p..size.saturating_sub(k)).step_by(2 * k) {
+                for i in 0..k.min(size - j - k) {
+                    if (i + j) / (2 * p) == (i + j + k) / (2 * p) {
+                        layer.push((i + j, i + j + k));
+                    }
+                }
+            }
+            network.push(layer);
+            k /= 2;
+        }
+        p *= 2;
+    }
+    truncate_network(network, len)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_layers_are_independent() {
+        for layer in network(100) {
+            let mut seen: Vec<usize> = layer.iter().flat_map(|&(i, j)| [i, j]).collect();
+            let len = seen.len();
+            seen.sort();
+            seen.dedup();
+            assert_eq!(seen.len(), len);
+            assert!(layer.iter().all(|&(i, j)| i < j && j < 100));
+        }
+    }
+}
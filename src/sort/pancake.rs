@@ -0,0 +1,79 @@
+use crate::sort::{count_operations, Complexity, Info, Operation, Snapshot, SortPointer};
+
+pub const INFO: Info = Info {
+    best: Complexity::Quadratic,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    for size in (2..=input.len()).rev() {
+        let mut max = 0;
+        for j in 1..size {
+            if input[j] > input[max] {
+                max = j;
+            }
+            result.push(
+                Snapshot::new(input.to_vec(), SortPointer(j, max))
+                    .with_operations(vec![Operation::Compare(j, max)])
+                    .with_phase("Find Largest"),
+            );
+        }
+        if max == size - 1 {
+            continue;
+        }
+        // Bring the largest to the front, then flip it down into place
+        if max > 0 {
+            flip(input, max + 1, &mut result);
+        }
+        flip(input, size, &mut result);
+    }
+    count_operations(result)
+}
+
+/// Reverses the first `count` elements, like a spatula under a stack.
+fn flip(input: &mut [f64], count: usize, result: &mut Vec<Snapshot>) {
+    input[..count].reverse();
+    result.push(
+        Snapshot::new(input.to_vec(), SortPointer(0, count - 1))
+            .with_operations(vec![Operation::Reverse(0, count)])
+            .with_phase("Flip"),
+    );
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        for mut items in [vec![], vec![1.0], vec![2.0, 1.0], vec![3.0, 1.0, 2.0, 1.0]] {
+            create_iterator(&mut items).for_each(drop);
+            assert!(test_util::is_sorted(&items));
+        }
+    }
+
+    #[test]
+    fn test_only_flips_move_elements() {
+        let items = generate_random_data(30);
+        let snapshot = create_iterator(&mut items.clone()).last().unwrap();
+        assert!(snapshot.counters.reversals <= 2 * 30);
+        assert_eq!(snapshot.counters.writes, 0);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::sort::{count_operations, Complexity, Info, Operation, Piles, Snapshot, SortPointer};
+
+pub const INFO: Info = Info {
+    best: Complexity::Linear,
+    average: Complexity::NLogN,
+    worst: Complexity::NLogN,
+    space: Complexity::Linear,
+    stable: false,
+    in_place: false,
+    adaptive: true,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let mut piles = Piles {
+        piles: vec![],
+        current: None,
+    };
+
+    // Deal each card onto the leftmost pile whose top is not smaller, so the
+    // tops stay in increasing order and can be binary searched
+    for i in 0..input.len() {
+        let (mut left, mut right) = (0, piles.piles.len());
+        let mut operations = vec![];
+        while left < right {
+            let mid = (left + right) / 2;
+            operations.push(Operation::Compare(i, mid));
+            if piles.piles[mid][piles.piles[mid].len() - 1] < input[i] {
+                left = mid + 1;
+            } else {
+                right = mid;
+            }
+        }
+        if left == piles.piles.len() {
+            piles.piles.push(vec![]);
+        }
+        piles.piles[left].push(input[i]);
+        piles.current = Some(left);
+        operations.push(Operation::Distribute {
+            index: i,
+            bucket: left,
+        });
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer(i, i))
+                .with_operations(operations)
+                .with_phase("Deal")
+                .with_piles(piles.clone())
+                .with_aux_memory(piles.piles.iter().map(Vec::len).sum()),
+        );
+    }
+
+    // Every pile is sorted with its smallest card on top, so repeatedly take
+    // the smallest top
+    for k in 0..input.len() {
+        let mut smallest = 0;
+        let mut operations = vec![];
+        for pile in 1..piles.piles.len() {
+            operations.push(Operation::Compare(pile, smallest));
+            if top(&piles.piles[pile]) < top(&piles.piles[smallest]) {
+                smallest = pile;
+            }
+        }
+        input[k] = piles.piles[smallest].pop().unwrap();
+        if piles.piles[smallest].is_empty() {
+            piles.piles.remove(smallest);
+            piles.current = None;
+        } else {
+            piles.current = Some(smallest);
+        }
+        operations.push(Operation::Write(k));
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer(k, k))
+                .with_operations(operations)
+                .with_phase("Collect")
+                .with_piles(piles.clone())
+                .with_aux_memory(piles.piles.iter().map(Vec::len).sum()),
+        );
+    }
+    count_operations(result)
+}
+
+fn top(pile: &[f64]) -> f64 {
+    pile[pile.len() - 1]
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        for mut items in [vec![], vec![1.0], vec![2.0, 1.0], vec![3.0, 1.0, 2.0, 1.0]] {
+            create_iterator(&mut items).for_each(drop);
+            assert!(test_util::is_sorted(&items));
+        }
+    }
+
+    #[test]
+    fn test_pile_count() {
+        // Dealing makes as many piles as the longest increasing subsequence
+        let mut items = vec![3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+        let dealt = create_iterator(&mut items).nth(7).unwrap();
+        assert_eq!(dealt.piles.unwrap().piles.len(), 4);
+    }
+}
@@ -0,0 +1,374 @@
+use crate::sort::{
+    count_operations, heap::heap_sort_range, insertion::insertion_sort_range, CallStack,
+    Complexity, Info, Operation, Snapshot, SortPointer,
+};
+use std::ops::Range;
+
+pub const INFO: Info = Info {
+    best: Complexity::Linear,
+    average: Complexity::NLogN,
+    worst: Complexity::NLogN,
+    space: Complexity::Log,
+    stable: false,
+    in_place: true,
+    adaptive: true,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut state = PdqState {
+        stack: CallStack::default(),
+        result: vec![],
+    };
+    let len = input.len();
+    let limit = (usize::BITS - len.leading_zeros()) as usize;
+    pdq_sort(input, 0..len, None, limit, true, true, &mut state);
+    count_operations(state.result)
+}
+
+/// Ranges this short are finished with insertion sort.
+const MAX_INSERTION: usize = 20;
+/// Ranges at least this long pick their pivot with Tukey's ninther.
+const SHORTEST_MEDIAN_OF_MEDIANS: usize = 50;
+/// Pivot selection performs at most this many swaps, which means the
+/// sampled elements were in descending order.
+const MAX_SWAPS: usize = 4 * 3;
+/// Partial insertion sort gives up after fixing this many out-of-order pairs.
+const MAX_STEPS: usize = 5;
+/// Partial insertion sort only shifts elements in ranges at least this long.
+const SHORTEST_SHIFTING: usize = 50;
+
+struct PdqState {
+    stack: CallStack,
+    result: Vec<Snapshot>,
+}
+
+impl PdqState {
+    fn compare(&mut self, input: &[f64], i: usize, j: usize, phase: &'static str) -> bool {
+        self.record(input, SortPointer(i, j), Operation::Compare(i, j), phase);
+        input[i] < input[j]
+    }
+    fn swap(&mut self, input: &mut [f64], i: usize, j: usize, phase: &'static str) {
+        input.swap(i, j);
+        self.record(input, SortPointer(i, j), Operation::Swap(i, j), phase);
+    }
+    fn record(
+        &mut self,
+        input: &[f64],
+        pointer: SortPointer,
+        operation: Operation,
+        phase: &'static str,
+    ) {
+        self.result.push(
+            Snapshot::new(input.to_vec(), pointer)
+                .with_operations(vec![operation])
+                .with_stack(self.stack.clone())
+                .with_phase(phase),
+        );
+    }
+}
+
+/// Sorts `range` following Rust's pattern-defeating quicksort. `pred` is the
+/// pivot of an enclosing partition that every element of `range` is at least
+/// as big as, `limit` is how many unbalanced partitions are allowed before
+/// switching to heap sort, and the flags describe the previous partition.
+fn pdq_sort(
+    input: &mut [f64],
+    range: Range<usize>,
+    pred: Option<f64>,
+    mut limit: usize,
+    was_balanced: bool,
+    was_partitioned: bool,
+    state: &mut PdqState,
+) {
+    let len = range.len();
+    if len < 2 {
+        return;
+    }
+    state.stack.push(range.start, range.end);
+
+    if len <= MAX_INSERTION {
+        let stack = state.stack.clone();
+        insertion_sort_range(
+            input,
+            range,
+            &|snapshot| snapshot.with_stack(stack.clone()).with_phase("Insertion"),
+            &mut state.result,
+        );
+        state.stack.pop();
+        return;
+    }
+    if limit == 0 {
+        let stack = state.stack.clone();
+        heap_sort_range(
+            input,
+            range,
+            &|snapshot| {
+                snapshot
+                    .with_stack(stack.clone())
+                    .with_phase("Heapsort Fallback")
+            },
+            &mut state.result,
+        );
+        state.stack.pop();
+        return;
+    }
+
+    // An unbalanced partition may come from a pattern in the input, so
+    // shuffle a few elements around to break it up
+    if !was_balanced {
+        break_patterns(input, range.clone(), state);
+        limit -= 1;
+    }
+
+    let (pivot, likely_sorted) = choose_pivot(input, range.clone(), state);
+    state.stack.set_pivot(pivot);
+
+    if was_balanced
+        && was_partitioned
+        && likely_sorted
+        && partial_insertion_sort(input, range.clone(), state)
+    {
+        state.stack.pop();
+        return;
+    }
+
+    // If the pivot equals the enclosing pivot, it is the smallest value in
+    // the range, so gather its duplicates and only sort what is left
+    if let Some(pred) = pred {
+        if pred >= input[pivot] {
+            let mid = partition_equal(input, range.clone(), pivot, state);
+            pdq_sort(input, mid..range.end, Some(pred), limit, true, true, state);
+            state.stack.pop();
+            return;
+        }
+    }
+
+    let (mid, was_partitioned) = partition(input, range.clone(), pivot, state);
+    state.stack.set_pivot(mid);
+    let was_balanced = (mid - range.start).min(range.end - mid) >= len / 8;
+    let pivot_value = input[mid];
+
+    pdq_sort(
+        input,
+        range.start..mid,
+        pred,
+        limit,
+        was_balanced,
+        was_partitioned,
+        state,
+    );
+    pdq_sort(
+        input,
+        mid + 1..range.end,
+        Some(pivot_value),
+        limit,
+        was_balanced,
+        was_partitioned,
+        state,
+    );
+    state.stack.pop();
+}
+
+/// Picks a pivot from a median of three, or a median of medians for long
+/// ranges. Many swaps while sorting the samples suggest the range is
+/// descending, in which case it is reversed. Returns the pivot and whether
+/// the range looks sorted.
+fn choose_pivot(input: &mut [f64], range: Range<usize>, state: &mut PdqState) -> (usize, bool) {
+    let len = range.len();
+    let (mut a, mut b, mut c) = (
+        range.start + len / 4,
+        range.start + len / 4 * 2,
+        range.start + len / 4 * 3,
+    );
+    let mut swaps = 0;
+
+    // Sorts the indices, not the elements, so nothing moves yet
+    let mut sort2 = |a: &mut usize, b: &mut usize, state: &mut PdqState| {
+        if state.compare(input, *b, *a, "Choose Pivot") {
+            std::mem::swap(a, b);
+            swaps += 1;
+        }
+    };
+    let mut sort3 = |a: &mut usize, b: &mut usize, c: &mut usize, state: &mut PdqState| {
+        sort2(a, b, state);
+        sort2(b, c, state);
+        sort2(a, b, state);
+    };
+
+    if len >= SHORTEST_MEDIAN_OF_MEDIANS {
+        for index in [&mut a, &mut b, &mut c] {
+            let (mut before, mut after) = (*index - 1, *index + 1);
+            sort3(&mut before, index, &mut after, state);
+        }
+    }
+    sort3(&mut a, &mut b, &mut c, state);
+
+    if swaps < MAX_SWAPS {
+        (b, swaps == 0)
+    } else {
+        let (mut i, mut j) = (range.start, range.end - 1);
+        while i < j {
+            state.swap(input, i, j, "Reverse");
+            i += 1;
+            j -= 1;
+        }
+        (range.start + range.end - 1 - b, true)
+    }
+}
+
+/// Fixes up a nearly sorted range by shifting a few out-of-order elements
+/// into place, giving up after `MAX_STEPS` of them. Returns whether the
+/// range ended up sorted.
+fn partial_insertion_sort(input: &mut [f64], range: Range<usize>, state: &mut PdqState) -> bool {
+    let mut i = range.start + 1;
+    for _ in 0..MAX_STEPS {
+        while i < range.end && !state.compare(input, i, i - 1, "Partial Insertion") {
+            i += 1;
+        }
+        if i == range.end {
+            return true;
+        }
+        if range.len() < SHORTEST_SHIFTING {
+            return false;
+        }
+        state.swap(input, i - 1, i, "Partial Insertion");
+
+        // Shift the smaller element left and the bigger one right
+        let mut j = i - 1;
+        while j > range.start && state.compare(input, j, j - 1, "Partial Insertion") {
+            state.swap(input, j, j - 1, "Partial Insertion");
+            j -= 1;
+        }
+        let mut j = i;
+        while j + 1 < range.end && state.compare(input, j + 1, j, "Partial Insertion") {
+            state.swap(input, j, j + 1, "Partial Insertion");
+            j += 1;
+        }
+    }
+    false
+}
+
+/// Swaps a few elements around the middle of the range with pseudo-random
+/// partners, using the same xorshift generator seeded by the length as Rust.
+fn break_patterns(input: &mut [f64], range: Range<usize>, state: &mut PdqState) {
+    let len = range.len();
+    let mut random = len as u32;
+    let mut next = || {
+        random ^= random << 13;
+        random ^= random >> 17;
+        random ^= random << 5;
+        random as usize
+    };
+    let modulus = len.next_power_of_two();
+    let pos = len / 4 * 2;
+    for i in 0..3 {
+        let mut other = next() & (modulus - 1);
+        if other >= len {
+            other -= len;
+        }
+        state.swap(
+            input,
+            range.start + pos - 1 + i,
+            range.start + other,
+            "Break Patterns",
+        );
+    }
+}
+
+/// Partitions `range` around `pivot` into elements smaller than it and
+/// elements at least as big, returning the pivot's final index and whether
+/// the range was already partitioned.
+fn partition(
+    input: &mut [f64],
+    range: Range<usize>,
+    pivot: usize,
+    state: &mut PdqState,
+) -> (usize, bool) {
+    let lo = range.start;
+    state.swap(input, lo, pivot, "Partition");
+
+    let (mut l, mut r) = (lo + 1, range.end);
+    while l < r && state.compare(input, l, lo, "Partition") {
+        l += 1;
+    }
+    while l < r && !state.compare(input, r - 1, lo, "Partition") {
+        r -= 1;
+    }
+    let was_partitioned = l >= r;
+
+    loop {
+        while l < r && state.compare(input, l, lo, "Partition") {
+            l += 1;
+        }
+        while l < r && !state.compare(input, r - 1, lo, "Partition") {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+        r -= 1;
+        state.swap(input, l, r, "Partition");
+        l += 1;
+    }
+
+    let mid = l - 1;
+    state.swap(input, lo, mid, "Partition");
+    (mid, was_partitioned)
+}
+
+/// Moves the elements equal to `pivot` to the front of `range`, given that
+/// none are smaller, and returns the index of the first bigger element.
+fn partition_equal(
+    input: &mut [f64],
+    range: Range<usize>,
+    pivot: usize,
+    state: &mut PdqState,
+) -> usize {
+    let lo = range.start;
+    state.swap(input, lo, pivot, "Partition Equal");
+
+    let (mut l, mut r) = (lo + 1, range.end);
+    loop {
+        while l < r && !state.compare(input, lo, l, "Partition Equal") {
+            l += 1;
+        }
+        while l < r && state.compare(input, lo, r - 1, "Partition Equal") {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+        r -= 1;
+        state.swap(input, l, r, "Partition Equal");
+        l += 1;
+    }
+    l
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_adversarial_inputs() {
+        let ascending: Vec<f64> = (0..200).map(|x| x as f64).collect();
+        let descending: Vec<f64> = ascending.iter().rev().cloned().collect();
+        let duplicates: Vec<f64> = (0..200).map(|x| (x % 3) as f64).collect();
+        for items in [ascending, descending, duplicates] {
+            let result = create_iterator(&mut items.clone()).last();
+            let snapshot = result.unwrap();
+            assert!(test_util::is_sorted(&snapshot.items));
+        }
+    }
+}
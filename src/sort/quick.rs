@@ -0,0 +1,482 @@
+use crate::sort::{
+    count_operations, intro::median_of_three, CallStack, Complexity, Info, Operation, Setting,
+    Snapshot, Sort, SortPointer, Stepper,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::NLogN,
+    worst: Complexity::Quadratic,
+    space: Complexity::Log,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub struct QuickSort {
+    pub stepper: Stepper,
+    pub pivot: PivotStrategy,
+    pub scheme: PartitionScheme,
+}
+
+impl QuickSort {
+    pub fn new(input: Vec<f64>) -> QuickSort {
+        let (pivot, scheme) = (PivotStrategy::Last, PartitionScheme::Lomuto);
+        let iterator = create_iterator(&mut input.clone(), pivot, scheme);
+        QuickSort {
+            stepper: Stepper::new(input, iterator),
+            pivot,
+            scheme,
+        }
+    }
+}
+
+impl Sort for QuickSort {
+    fn stepper(&self) -> &Stepper {
+        &self.stepper
+    }
+    fn stepper_mut(&mut self) -> &mut Stepper {
+        &mut self.stepper
+    }
+    fn get_name(&self) -> String {
+        "Quick Sort".to_string()
+    }
+    fn info(&self) -> &'static Info {
+        &INFO
+    }
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+        create_iterator(input, self.pivot, self.scheme)
+    }
+    fn is_recursive(&self) -> bool {
+        true
+    }
+    fn settings(&self) -> Vec<Setting> {
+        vec![
+            Setting {
+                name: "pivot",
+                value: self.pivot.get_name(),
+            },
+            Setting {
+                name: "partition",
+                value: self.scheme.get_name(),
+            },
+        ]
+    }
+    fn cycle_setting(&mut self, name: &str) {
+        match name {
+            "pivot" => self.pivot = self.pivot.next(),
+            "partition" => self.scheme = self.scheme.next(),
+            _ => return,
+        }
+        self.reset(self.stepper.input.clone());
+    }
+}
+
+/// How the pivot of each partition is chosen.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PivotStrategy {
+    First,
+    Last,
+    Middle,
+    Random,
+    MedianOfThree,
+    Ninther,
+}
+
+impl PivotStrategy {
+    pub fn next(&self) -> PivotStrategy {
+        match self {
+            PivotStrategy::First => PivotStrategy::Last,
+            PivotStrategy::Last => PivotStrategy::Middle,
+            PivotStrategy::Middle => PivotStrategy::Random,
+            PivotStrategy::Random => PivotStrategy::MedianOfThree,
+            PivotStrategy::MedianOfThree => PivotStrategy::Ninther,
+            PivotStrategy::Ninther => PivotStrategy::First,
+        }
+    }
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PivotStrategy::First => "First",
+            PivotStrategy::Last => "Last",
+            PivotStrategy::Middle => "Middle",
+            PivotStrategy::Random => "Random",
+            PivotStrategy::MedianOfThree => "Median-of-3",
+            PivotStrategy::Ninther => "Ninther",
+        }
+    }
+}
+
+/// How a range is split around its pivots.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PartitionScheme {
+    Lomuto,
+    Hoare,
+    DualPivot,
+}
+
+impl PartitionScheme {
+    pub fn next(&self) -> PartitionScheme {
+        match self {
+            PartitionScheme::Lomuto => PartitionScheme::Hoare,
+            PartitionScheme::Hoare => PartitionScheme::DualPivot,
+            PartitionScheme::DualPivot => PartitionScheme::Lomuto,
+        }
+    }
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PartitionScheme::Lomuto => "Lomuto",
+            PartitionScheme::Hoare => "Hoare",
+            PartitionScheme::DualPivot => "Dual-Pivot",
+        }
+    }
+}
+
+pub fn create_iterator(
+    input: &mut [f64],
+    pivot: PivotStrategy,
+    scheme: PartitionScheme,
+) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let mut stack = CallStack::default();
+    quick_sort(
+        input,
+        &mut stack,
+        0,
+        input.len(),
+        pivot,
+        scheme,
+        &mut result,
+    );
+    count_operations(result)
+}
+
+fn quick_sort(
+    input: &mut [f64],
+    stack: &mut CallStack,
+    lo: usize,
+    hi: usize,
+    pivot: PivotStrategy,
+    scheme: PartitionScheme,
+    result: &mut Vec<Snapshot>,
+) {
+    if hi - lo < 2 {
+        return;
+    }
+    stack.push(lo, hi);
+    let chosen = choose_pivot(
+        input,
+        lo,
+        hi,
+        pivot,
+        &|snapshot| snapshot.with_stack(stack.clone()),
+        result,
+    );
+
+    // Every scheme expects its pivot at a fixed end of the range
+    let target = match scheme {
+        PartitionScheme::Lomuto => hi - 1,
+        PartitionScheme::Hoare | PartitionScheme::DualPivot => lo,
+    };
+    stack.set_pivot(target);
+    if chosen != target {
+        input.swap(chosen, target);
+        result.push(
+            Snapshot::new(input.to_vec(), SortPointer(chosen, target))
+                .with_operations(vec![Operation::Swap(chosen, target)])
+                .with_pivots(vec![target])
+                .with_stack(stack.clone()),
+        );
+    }
+
+    let decorate = |snapshot: Snapshot| snapshot.with_stack(stack.clone());
+    let ranges = match scheme {
+        PartitionScheme::Lomuto => {
+            let store = lomuto_partition(input, lo, hi, &decorate, result);
+            stack.set_pivot(store);
+            vec![(lo, store), (store + 1, hi)]
+        }
+        PartitionScheme::Hoare => {
+            let split = hoare_partition(input, lo, hi, &decorate, result);
+            vec![(lo, split), (split, hi)]
+        }
+        PartitionScheme::DualPivot => {
+            let (lt, gt) = dual_pivot_partition(input, lo, hi, &decorate, result);
+            stack.set_pivot(lt);
+            vec![(lo, lt), (lt + 1, gt), (gt + 1, hi)]
+        }
+    };
+
+    for (lo, hi) in ranges {
+        quick_sort(input, stack, lo, hi, pivot, scheme, result);
+    }
+    stack.pop();
+}
+
+/// Picks the index of the pivot for `lo..hi`, recording the comparisons the
+/// strategy needs.
+fn choose_pivot(
+    input: &[f64],
+    lo: usize,
+    hi: usize,
+    pivot: PivotStrategy,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) -> usize {
+    let mid = lo + (hi - lo) / 2;
+    match pivot {
+        PivotStrategy::First => lo,
+        PivotStrategy::Last => hi - 1,
+        PivotStrategy::Middle => mid,
+        PivotStrategy::Random => {
+            use rand::Rng;
+            rand::thread_rng().gen_range(lo..hi)
+        }
+        // Tukey's median of the medians of three evenly spread triples
+        PivotStrategy::Ninther if hi - lo >= 9 => {
+            let step = (hi - lo) / 8;
+            let first = median_of_three(input, lo, lo + step, lo + 2 * step, decorate, result);
+            let middle = median_of_three(input, mid - step, mid, mid + step, decorate, result);
+            let last = median_of_three(
+                input,
+                hi - 1 - 2 * step,
+                hi - 1 - step,
+                hi - 1,
+                decorate,
+                result,
+            );
+            median_of_three(input, first, middle, last, decorate, result)
+        }
+        PivotStrategy::MedianOfThree | PivotStrategy::Ninther => {
+            median_of_three(input, lo, mid, hi - 1, decorate, result)
+        }
+    }
+}
+
+/// Lomuto partition of `lo..hi` around the last element of the range,
+/// returning the pivot's final index.
+pub fn lomuto_partition(
+    input: &mut [f64],
+    lo: usize,
+    hi: usize,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) -> usize {
+    let pivot = hi - 1;
+    let mut store = lo;
+    for j in lo..pivot {
+        let mut operations = vec![Operation::Compare(j, pivot)];
+        if input[j] < input[pivot] {
+            if store != j {
+                input.swap(store, j);
+                operations.push(Operation::Swap(store, j));
+            }
+            store += 1;
+        }
+        result.push(decorate(
+            Snapshot::new(input.to_vec(), SortPointer(j, pivot))
+                .with_operations(operations)
+                .with_pivots(vec![pivot])
+                .with_boundaries(vec![store, j + 1]),
+        ));
+    }
+    let mut operations = vec![];
+    if store != pivot {
+        input.swap(store, pivot);
+        operations.push(Operation::Swap(store, pivot));
+    }
+    result.push(decorate(
+        Snapshot::new(input.to_vec(), SortPointer(store, pivot))
+            .with_operations(operations)
+            .with_pivots(vec![store])
+            .with_boundaries(vec![store, store + 1]),
+    ));
+    store
+}
+
+/// Hoare partition of `lo..hi` around the value of its first element,
+/// scanning in from both ends and swapping pairs on the wrong side. Returns
+/// the index where the right part begins, which may not hold the pivot.
+fn hoare_partition(
+    input: &mut [f64],
+    lo: usize,
+    hi: usize,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) -> usize {
+    let pivot_value = input[lo];
+    let mut pivot = lo;
+    let (mut i, mut j) = (lo, hi - 1);
+    loop {
+        loop {
+            result.push(decorate(
+                Snapshot::new(input.to_vec(), SortPointer(i, pivot))
+                    .with_operations(vec![Operation::Compare(i, pivot)])
+                    .with_pivots(vec![pivot])
+                    .with_boundaries(vec![i, j + 1]),
+            ));
+            if input[i] >= pivot_value {
+                break;
+            }
+            i += 1;
+        }
+        loop {
+            result.push(decorate(
+                Snapshot::new(input.to_vec(), SortPointer(j, pivot))
+                    .with_operations(vec![Operation::Compare(j, pivot)])
+                    .with_pivots(vec![pivot])
+                    .with_boundaries(vec![i, j + 1]),
+            ));
+            if input[j] <= pivot_value {
+                break;
+            }
+            j -= 1;
+        }
+        if i >= j {
+            return j + 1;
+        }
+
+        input.swap(i, j);
+        // The pivot itself can be swapped across, so follow it
+        if pivot == i {
+            pivot = j;
+        } else if pivot == j {
+            pivot = i;
+        }
+        result.push(decorate(
+            Snapshot::new(input.to_vec(), SortPointer(i, j))
+                .with_operations(vec![Operation::Swap(i, j)])
+                .with_pivots(vec![pivot])
+                .with_boundaries(vec![i + 1, j]),
+        ));
+        i += 1;
+        j -= 1;
+    }
+}
+
+/// Yaroslavskiy's dual-pivot partition of `lo..hi` around its first and
+/// last elements into values below, between and above the two pivots.
+/// Returns the final indices of the two pivots.
+fn dual_pivot_partition(
+    input: &mut [f64],
+    lo: usize,
+    hi: usize,
+    decorate: &dyn Fn(Snapshot) -> Snapshot,
+    result: &mut Vec<Snapshot>,
+) -> (usize, usize) {
+    let last = hi - 1;
+    let mut operations = vec![Operation::Compare(lo, last)];
+    if input[last] < input[lo] {
+        input.swap(lo, last);
+        operations.push(Operation::Swap(lo, last));
+    }
+    result.push(decorate(
+        Snapshot::new(input.to_vec(), SortPointer(lo, last))
+            .with_operations(operations)
+            .with_pivots(vec![lo, last]),
+    ));
+
+    let (low, high) = (input[lo], input[last]);
+    let (mut lt, mut k, mut gt) = (lo + 1, lo + 1, last - 1);
+    while k <= gt {
+        let mut operations = vec![Operation::Compare(k, lo)];
+        if input[k] < low {
+            input.swap(k, lt);
+            operations.push(Operation::Swap(k, lt));
+            lt += 1;
+        } else {
+            operations.push(Operation::Compare(k, last));
+            if input[k] > high {
+                while input[gt] > high && k < gt {
+                    operations.push(Operation::Compare(gt, last));
+                    gt -= 1;
+                }
+                input.swap(k, gt);
+                operations.push(Operation::Swap(k, gt));
+                gt -= 1;
+                operations.push(Operation::Compare(k, lo));
+                if input[k] < low {
+                    input.swap(k, lt);
+                    operations.push(Operation::Swap(k, lt));
+                    lt += 1;
+                }
+            }
+        }
+        result.push(decorate(
+            Snapshot::new(input.to_vec(), SortPointer(k, gt + 1))
+                .with_operations(operations)
+                .with_pivots(vec![lo, last])
+                .with_boundaries(vec![lt, k + 1, gt + 1]),
+        ));
+        k += 1;
+    }
+
+    // Move the pivots between the regions they bound
+    let (lt, gt) = (lt - 1, gt + 1);
+    let mut operations = vec![];
+    for (pivot, target) in [(lo, lt), (last, gt)] {
+        if pivot != target {
+            input.swap(pivot, target);
+            operations.push(Operation::Swap(pivot, target));
+        }
+    }
+    result.push(decorate(
+        Snapshot::new(input.to_vec(), SortPointer(lt, gt))
+            .with_operations(operations)
+            .with_pivots(vec![lt, gt])
+            .with_boundaries(vec![lt, lt + 1, gt, gt + 1]),
+    ));
+    (lt, gt)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    const SCHEMES: [PartitionScheme; 3] = [
+        PartitionScheme::Lomuto,
+        PartitionScheme::Hoare,
+        PartitionScheme::DualPivot,
+    ];
+
+    #[test]
+    fn test_final_sort() {
+        let mut pivot = PivotStrategy::First;
+        loop {
+            for scheme in SCHEMES {
+                let items = generate_random_data(100);
+                let result = create_iterator(&mut items.clone(), pivot, scheme).last();
+                let snapshot = result.unwrap();
+                let sorted = test_util::is_sorted(&snapshot.items);
+                assert!(sorted);
+            }
+            pivot = pivot.next();
+            if pivot == PivotStrategy::First {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        for scheme in SCHEMES {
+            for mut items in [vec![], vec![1.0], vec![2.0, 1.0], vec![1.0, 1.0, 1.0, 0.0]] {
+                create_iterator(&mut items, PivotStrategy::MedianOfThree, scheme).for_each(drop);
+                assert!(test_util::is_sorted(&items));
+            }
+        }
+    }
+
+    #[test]
+    fn test_median_of_three_on_sorted_input() {
+        // A fixed end pivot makes sorted input quadratic, the median does not
+        let comparisons = |pivot| {
+            let mut items: Vec<f64> = (0..100).map(|x| x as f64).collect();
+            let result = create_iterator(&mut items, pivot, PartitionScheme::Lomuto).last();
+            result.unwrap().counters.comparisons
+        };
+        assert_eq!(comparisons(PivotStrategy::Last), 99 * 100 / 2);
+        assert!(comparisons(PivotStrategy::MedianOfThree) < 1000);
+    }
+}
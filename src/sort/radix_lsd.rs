@@ -0,0 +1,108 @@
+use crate::sort::{
+    count_operations, counting::counting_pass, key, AuxArray, CallStack, Complexity, Histogram,
+    Info, Snapshot, Sort, Stepper,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::Digits,
+    average: Complexity::Digits,
+    worst: Complexity::Digits,
+    space: Complexity::LinearKeys,
+    stable: true,
+    in_place: false,
+    adaptive: false,
+    online: false,
+};
+
+pub struct LsdRadixSort {
+    pub stepper: Stepper,
+    pub base: usize,
+}
+
+impl LsdRadixSort {
+    pub fn new(input: Vec<f64>, base: usize) -> LsdRadixSort {
+        let iterator = create_iterator(&mut input.clone(), base);
+        LsdRadixSort {
+            stepper: Stepper::new(input, iterator),
+            base,
+        }
+    }
+}
+
+impl Sort for LsdRadixSort {
+    fn stepper(&self) -> &Stepper {
+        &self.stepper
+    }
+    fn stepper_mut(&mut self) -> &mut Stepper {
+        &mut self.stepper
+    }
+    fn get_name(&self) -> String {
+        format!("LSD Radix Sort (base {})", self.base)
+    }
+    fn info(&self) -> &'static Info {
+        &INFO
+    }
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+        create_iterator(input, self.base)
+    }
+}
+
+pub fn create_iterator(input: &mut [f64], base: usize) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let digits = digit_count(input, base);
+    let mut output = AuxArray::new("Output", input.len());
+    let len = input.len();
+    let mut place = 1;
+    for digit in 0..digits {
+        let mut histogram = Histogram::new(
+            format!("Digit {} of {} (base {})", digit + 1, digits, base),
+            base,
+        );
+        counting_pass(
+            input,
+            0..len,
+            &mut output,
+            &mut histogram,
+            |x| key(x) / place % base,
+            &CallStack::default(),
+            &mut result,
+        );
+        place *= base;
+    }
+    count_operations(result)
+}
+
+/// The number of base `base` digits in the largest key of `input`.
+pub fn digit_count(input: &[f64], base: usize) -> usize {
+    let mut max = input.iter().map(|x| key(*x)).max().unwrap_or(0);
+    let mut digits = 1;
+    while max >= base {
+        max /= base;
+        digits += 1;
+    }
+    digits
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone(), 10).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_final_sort_base_2() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone(), 2).last();
+        let snapshot = result.unwrap();
+        assert!(test_util::is_sorted(&snapshot.items));
+        assert_eq!(digit_count(&[99.0], 2), 7);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::sort::{
+    count_operations, counting::counting_pass, key, radix_lsd::digit_count, AuxArray, CallStack,
+    Complexity, Histogram, Info, Snapshot,
+};
+use std::ops::Range;
+
+pub const INFO: Info = Info {
+    best: Complexity::Digits,
+    average: Complexity::Digits,
+    worst: Complexity::Digits,
+    space: Complexity::LinearKeys,
+    stable: true,
+    in_place: false,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let digits = digit_count(input, BASE);
+    let mut output = AuxArray::new("Output", input.len());
+    let mut stack = CallStack::default();
+    let len = input.len();
+    msd_sort(
+        input,
+        &mut output,
+        &mut stack,
+        0..len,
+        digits,
+        0,
+        &mut result,
+    );
+    count_operations(result)
+}
+
+const BASE: usize = 10;
+
+fn msd_sort(
+    input: &mut [f64],
+    output: &mut AuxArray,
+    stack: &mut CallStack,
+    range: Range<usize>,
+    digits: usize,
+    digit: usize,
+    result: &mut Vec<Snapshot>,
+) {
+    if range.len() < 2 || digit >= digits {
+        return;
+    }
+    stack.push(range.start, range.end);
+
+    let place = BASE.pow((digits - digit - 1) as u32);
+    let mut histogram = Histogram::new(
+        format!("Digit {} of {} (base {})", digit + 1, digits, BASE),
+        BASE,
+    );
+    counting_pass(
+        input,
+        range.clone(),
+        output,
+        &mut histogram,
+        |x| key(x) / place % BASE,
+        stack,
+        result,
+    );
+
+    // Each bucket now holds a contiguous run sharing this digit
+    for bucket in 0..BASE {
+        let start = range.start + histogram.counts[bucket];
+        let end = match histogram.counts.get(bucket + 1) {
+            Some(next) => range.start + next,
+            None => range.end,
+        };
+        msd_sort(input, output, stack, start..end, digits, digit + 1, result);
+    }
+    stack.pop();
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::sort::{
+    count_operations, Complexity, Info, Operation, Snapshot, Sort, SortPointer, Stepper, Variant,
+};
+
+pub const SHELL_INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: true,
+    online: false,
+};
+
+pub const KNUTH_INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::ThreeHalves,
+    worst: Complexity::ThreeHalves,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: true,
+    online: false,
+};
+
+pub const SEDGEWICK_INFO: Info = Info {
+    best: Complexity::NLogN,
+    average: Complexity::FourThirds,
+    worst: Complexity::FourThirds,
+    space: Complexity::Constant,
+    stable: false,
+    in_place: true,
+    adaptive: true,
+    online: false,
+};
+
+pub struct ShellSort {
+    pub stepper: Stepper,
+    pub sequence: GapSequence,
+    pub variants: Vec<Variant>,
+}
+
+impl ShellSort {
+    pub fn new(input: Vec<f64>, sequence: GapSequence) -> ShellSort {
+        let iterator = create_iterator(&mut input.clone(), sequence);
+        ShellSort {
+            variants: variants(&input, sequence),
+            stepper: Stepper::new(input, iterator),
+            sequence,
+        }
+    }
+}
+
+impl Sort for ShellSort {
+    fn stepper(&self) -> &Stepper {
+        &self.stepper
+    }
+    fn stepper_mut(&mut self) -> &mut Stepper {
+        &mut self.stepper
+    }
+    fn get_name(&self) -> String {
+        format!("Shell Sort ({})", self.sequence.get_name())
+    }
+    fn info(&self) -> &'static Info {
+        self.sequence.info()
+    }
+    fn create_iterator(&self, input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+        create_iterator(input, self.sequence)
+    }
+    fn reset(&mut self, items: Vec<f64>) {
+        self.variants = variants(&items, self.sequence);
+        let iterator = self.create_iterator(&mut items.clone());
+        self.stepper.restart(items, iterator);
+    }
+    fn variants(&self) -> &[Variant] {
+        &self.variants
+    }
+}
+
+/// The gap sequences Shell sort can run with, from Shell's original halving
+/// to the empirically tuned Ciura sequence.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GapSequence {
+    Shell,
+    Knuth,
+    Sedgewick,
+    Ciura,
+    Tokuda,
+}
+
+impl GapSequence {
+    pub const ALL: [GapSequence; 5] = [
+        GapSequence::Shell,
+        GapSequence::Knuth,
+        GapSequence::Sedgewick,
+        GapSequence::Ciura,
+        GapSequence::Tokuda,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GapSequence::Shell => "Shell",
+            GapSequence::Knuth => "Knuth",
+            GapSequence::Sedgewick => "Sedgewick",
+            GapSequence::Ciura => "Ciura",
+            GapSequence::Tokuda => "Tokuda",
+        }
+    }
+
+    /// Ciura's and Tokuda's sequences have no proven bounds, but measure
+    /// no worse than Sedgewick's.
+    pub const fn info(&self) -> &'static Info {
+        match self {
+            GapSequence::Shell => &SHELL_INFO,
+            GapSequence::Knuth => &KNUTH_INFO,
+            GapSequence::Sedgewick | GapSequence::Ciura | GapSequence::Tokuda => &SEDGEWICK_INFO,
+        }
+    }
+
+    /// The gaps used to sort `len` elements, largest first and ending in 1.
+    pub fn gaps(&self, len: usize) -> Vec<usize> {
+        let mut gaps = match self {
+            GapSequence::Shell => {
+                let mut gaps = vec![];
+                let mut gap = len / 2;
+                while gap > 0 {
+                    gaps.push(gap);
+                    gap /= 2;
+                }
+                return if gaps.is_empty() { vec![1] } else { gaps };
+            }
+            // (3^k - 1) / 2, kept below a third of the length as Knuth advises
+            GapSequence::Knuth => {
+                let mut gaps = vec![1];
+                while gaps[gaps.len() - 1] * 3 + 1 < len.div_ceil(3) {
+                    gaps.push(gaps[gaps.len() - 1] * 3 + 1);
+                }
+                gaps
+            }
+            // 4^k + 3 * 2^(k - 1) + 1, prefixed with 1
+            GapSequence::Sedgewick => {
+                let mut gaps = vec![1];
+                for k in 1.. {
+                    let gap = 4usize.pow(k) + 3 * 2usize.pow(k - 1) + 1;
+                    if gap >= len {
+                        break;
+                    }
+                    gaps.push(gap);
+                }
+                gaps
+            }
+            // Found experimentally, extended by a factor of 2.25
+            GapSequence::Ciura => {
+                let mut gaps = vec![1, 4, 10, 23, 57, 132, 301, 701];
+                while gaps[gaps.len() - 1] < len {
+                    gaps.push((gaps[gaps.len() - 1] as f64 * 2.25) as usize);
+                }
+                gaps.into_iter()
+                    .filter(|&gap| gap == 1 || gap < len)
+                    .collect()
+            }
+            // ceil((9^k - 4^k) / (5 * 4^(k - 1)))
+            GapSequence::Tokuda => {
+                let mut gaps = vec![];
+                for k in 1.. {
+                    let gap =
+                        ((9f64.powi(k) - 4f64.powi(k)) / (5.0 * 4f64.powi(k - 1))).ceil() as usize;
+                    if gap >= len && !gaps.is_empty() {
+                        break;
+                    }
+                    gaps.push(gap);
+                }
+                gaps
+            }
+        };
+        gaps.reverse();
+        gaps
+    }
+}
+
+pub fn create_iterator(
+    input: &mut [f64],
+    sequence: GapSequence,
+) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    for gap in sequence.gaps(input.len()) {
+        for i in gap..input.len() {
+            let mut j = i;
+            while j >= gap {
+                let mut operations = vec![Operation::Compare(j - gap, j)];
+                let shifted = input[j] < input[j - gap];
+                if shifted {
+                    input.swap(j, j - gap);
+                    operations.push(Operation::Swap(j, j - gap));
+                    j -= gap;
+                }
+                result.push(
+                    Snapshot::new(input.to_vec(), SortPointer(i, j))
+                        .with_operations(operations)
+                        .with_gap(gap),
+                );
+                if !shifted {
+                    break;
+                }
+            }
+        }
+    }
+    count_operations(result)
+}
+
+/// The operation totals of every gap sequence over `input`.
+fn variants(input: &[f64], sequence: GapSequence) -> Vec<Variant> {
+    GapSequence::ALL
+        .iter()
+        .map(|&other| Variant {
+            name: other.get_name(),
+            counters: create_iterator(&mut input.to_vec(), other)
+                .last()
+                .map(|snapshot| snapshot.counters)
+                .unwrap_or_default(),
+            current: other == sequence,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        for sequence in GapSequence::ALL {
+            let items = generate_random_data(100);
+            let result = create_iterator(&mut items.clone(), sequence).last();
+            let snapshot = result.unwrap();
+            let sorted = test_util::is_sorted(&snapshot.items);
+            assert!(sorted);
+        }
+    }
+
+    #[test]
+    fn test_gaps() {
+        assert_eq!(GapSequence::Shell.gaps(100), vec![50, 25, 12, 6, 3, 1]);
+        assert_eq!(GapSequence::Knuth.gaps(100), vec![13, 4, 1]);
+        assert_eq!(GapSequence::Sedgewick.gaps(100), vec![77, 23, 8, 1]);
+        assert_eq!(GapSequence::Ciura.gaps(100), vec![57, 23, 10, 4, 1]);
+        assert_eq!(GapSequence::Tokuda.gaps(100), vec![46, 20, 9, 4, 1]);
+        for sequence in GapSequence::ALL {
+            assert_eq!(sequence.gaps(1), vec![1]);
+        }
+    }
+}
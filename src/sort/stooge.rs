@@ -0,0 +1,78 @@
+use crate::sort::{
+    count_operations, CallStack, Complexity, Info, Operation, Snapshot, SortPointer,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::Stooge,
+    average: Complexity::Stooge,
+    worst: Complexity::Stooge,
+    space: Complexity::Log,
+    stable: false,
+    in_place: true,
+    adaptive: false,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let mut stack = CallStack::default();
+    stooge_sort(input, &mut stack, 0, input.len(), &mut result);
+    count_operations(result)
+}
+
+/// Orders the ends of `lo..hi`, then sorts the first two thirds, the last
+/// two thirds and the first two thirds again.
+fn stooge_sort(
+    input: &mut [f64],
+    stack: &mut CallStack,
+    lo: usize,
+    hi: usize,
+    result: &mut Vec<Snapshot>,
+) {
+    if hi - lo < 2 {
+        return;
+    }
+    stack.push(lo, hi);
+    let last = hi - 1;
+    let mut operations = vec![Operation::Compare(lo, last)];
+    if input[last] < input[lo] {
+        input.swap(lo, last);
+        operations.push(Operation::Swap(lo, last));
+    }
+    result.push(
+        Snapshot::new(input.to_vec(), SortPointer(lo, last))
+            .with_operations(operations)
+            .with_stack(stack.clone()),
+    );
+
+    if hi - lo > 2 {
+        let third = (hi - lo) / 3;
+        stooge_sort(input, stack, lo, hi - third, result);
+        stooge_sort(input, stack, lo + third, hi, result);
+        stooge_sort(input, stack, lo, hi - third, result);
+    }
+    stack.pop();
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        for mut items in [vec![], vec![1.0], vec![2.0, 1.0], vec![3.0, 1.0, 2.0, 1.0]] {
+            create_iterator(&mut items).for_each(drop);
+            assert!(test_util::is_sorted(&items));
+        }
+    }
+}
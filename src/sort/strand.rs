@@ -0,0 +1,102 @@
+use crate::sort::{count_operations, AuxArray, Complexity, Info, Operation, Snapshot, SortPointer};
+
+pub const INFO: Info = Info {
+    best: Complexity::Linear,
+    average: Complexity::Quadratic,
+    worst: Complexity::Quadratic,
+    space: Complexity::Linear,
+    stable: true,
+    in_place: false,
+    adaptive: true,
+    online: false,
+};
+
+/// Pulls an increasing strand out of the unsorted elements at the front of
+/// the array, then merges it into the sorted elements gathered at the back.
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut result = vec![];
+    let len = input.len();
+    let mut remaining = len;
+    while remaining > 0 {
+        let mut strand = AuxArray::new("Strand", len);
+        let mut strand_len = 0;
+        let mut kept = 0;
+        for i in 0..remaining {
+            let mut operations = vec![];
+            let joins = strand_len == 0 || {
+                operations.push(Operation::Compare(i, strand_len - 1));
+                input[i] >= strand.items[strand_len - 1].unwrap()
+            };
+            if joins {
+                strand.set(strand_len, input[i]);
+                operations.push(Operation::AuxWrite(strand_len));
+                strand_len += 1;
+            } else {
+                input[kept] = input[i];
+                if kept != i {
+                    operations.push(Operation::Write(kept));
+                }
+                kept += 1;
+            }
+            result.push(
+                Snapshot::new(input.to_vec(), SortPointer(i, kept.saturating_sub(1)))
+                    .with_operations(operations)
+                    .with_phase("Extract Strand")
+                    .with_aux(vec![strand.clone()]),
+            );
+        }
+
+        // The strand fills the gap it left between the unsorted front and
+        // the sorted back, so the merge never overwrites an unread element
+        let (mut a, mut b) = (0, remaining);
+        for k in kept..len {
+            let mut operations = vec![];
+            let from_strand = a < strand_len
+                && (b == len || {
+                    operations.push(Operation::Compare(a, b));
+                    strand.items[a].unwrap() <= input[b]
+                });
+            if from_strand {
+                input[k] = strand.items[a].unwrap();
+                strand.items[a] = None;
+                a += 1;
+            } else {
+                input[k] = input[b];
+                b += 1;
+            }
+            strand.write = None;
+            operations.push(Operation::Write(k));
+            result.push(
+                Snapshot::new(input.to_vec(), SortPointer(k, k))
+                    .with_operations(operations)
+                    .with_phase("Merge")
+                    .with_aux(vec![strand.clone()]),
+            );
+        }
+        remaining = kept;
+    }
+    count_operations(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        for mut items in [vec![], vec![1.0], vec![2.0, 1.0], vec![3.0, 1.0, 2.0, 1.0]] {
+            create_iterator(&mut items).for_each(drop);
+            assert!(test_util::is_sorted(&items));
+        }
+    }
+}
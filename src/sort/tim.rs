@@ -0,0 +1,674 @@
+use crate::sort::{
+    count_operations, AuxArray, CallStack, Complexity, Info, Operation, Snapshot, SortPointer,
+};
+
+pub const INFO: Info = Info {
+    best: Complexity::Linear,
+    average: Complexity::NLogN,
+    worst: Complexity::NLogN,
+    space: Complexity::Linear,
+    stable: true,
+    in_place: false,
+    adaptive: true,
+    online: false,
+};
+
+pub fn create_iterator(input: &mut [f64]) -> Box<dyn Iterator<Item = Snapshot>> {
+    let mut state = TimSortState {
+        buffer: AuxArray::new("Merge Buffer", input.len()),
+        runs: CallStack::named("Run Stack"),
+        result: vec![],
+        min_gallop: MIN_GALLOP,
+    };
+    let len = input.len();
+    let min_run = min_run_length(len);
+
+    let mut lo = 0;
+    while lo < len {
+        let mut run = count_run(input, lo, &mut state);
+        if run < min_run {
+            let forced = min_run.min(len - lo);
+            binary_insertion_sort(input, lo, lo + run, lo + forced, &mut state);
+            run = forced;
+        }
+        state.runs.push(lo, lo + run);
+        merge_collapse(input, &mut state);
+        lo += run;
+    }
+
+    // Merge whatever is left on the stack, newest runs first
+    while state.runs.frames.len() > 1 {
+        let mut n = state.runs.frames.len() - 2;
+        if n > 0 && run_length(&state.runs, n - 1) < run_length(&state.runs, n + 1) {
+            n -= 1;
+        }
+        merge_at(input, n, &mut state);
+    }
+    count_operations(state.result)
+}
+
+/// Scaled down from CPython's 64 so the small arrays shown on screen still
+/// split into several runs.
+const MIN_MERGE: usize = 16;
+const MIN_GALLOP: usize = 7;
+
+struct TimSortState {
+    buffer: AuxArray,
+    runs: CallStack,
+    result: Vec<Snapshot>,
+    /// How many wins in a row start galloping, adjusted as the merges go.
+    min_gallop: usize,
+}
+
+impl TimSortState {
+    fn record(
+        &mut self,
+        input: &[f64],
+        pointer: SortPointer,
+        operations: Vec<Operation>,
+        phase: &'static str,
+    ) {
+        self.result.push(
+            Snapshot::new(input.to_vec(), pointer)
+                .with_operations(operations)
+                .with_aux(vec![self.buffer.clone()])
+                .with_stack(self.runs.clone())
+                .with_phase(phase),
+        );
+    }
+}
+
+/// Picks a run length between `MIN_MERGE / 2` and `MIN_MERGE` such that
+/// `len / min_run` is a power of two or just under one.
+pub fn min_run_length(mut len: usize) -> usize {
+    let mut remainder = 0;
+    while len >= MIN_MERGE {
+        remainder |= len & 1;
+        len >>= 1;
+    }
+    len + remainder
+}
+
+fn run_length(runs: &CallStack, index: usize) -> usize {
+    runs.frames[index].hi - runs.frames[index].lo
+}
+
+/// Finds the length of the natural run starting at `lo`, reversing it in
+/// place when it is strictly descending.
+fn count_run(input: &mut [f64], lo: usize, state: &mut TimSortState) -> usize {
+    let len = input.len();
+    if lo + 1 >= len {
+        return len - lo;
+    }
+    let descending = input[lo + 1] < input[lo];
+    state.record(
+        input,
+        SortPointer(lo, lo + 1),
+        vec![Operation::Compare(lo, lo + 1)],
+        "Run Detection",
+    );
+    let mut hi = lo + 2;
+    while hi < len {
+        let continues = if descending {
+            input[hi] < input[hi - 1]
+        } else {
+            input[hi] >= input[hi - 1]
+        };
+        state.record(
+            input,
+            SortPointer(hi - 1, hi),
+            vec![Operation::Compare(hi - 1, hi)],
+            "Run Detection",
+        );
+        if !continues {
+            break;
+        }
+        hi += 1;
+    }
+
+    if descending {
+        let (mut i, mut j) = (lo, hi - 1);
+        while i < j {
+            input.swap(i, j);
+            state.record(
+                input,
+                SortPointer(i, j),
+                vec![Operation::Swap(i, j)],
+                "Reverse Run",
+            );
+            i += 1;
+            j -= 1;
+        }
+    }
+    hi - lo
+}
+
+/// Extends the sorted run `lo..start` to `lo..hi`, finding each insertion
+/// point by binary search.
+fn binary_insertion_sort(
+    input: &mut [f64],
+    lo: usize,
+    start: usize,
+    hi: usize,
+    state: &mut TimSortState,
+) {
+    for i in start.max(lo + 1)..hi {
+        let (mut left, mut right) = (lo, i);
+        while left < right {
+            let mid = (left + right) / 2;
+            state.record(
+                input,
+                SortPointer(mid, i),
+                vec![Operation::Compare(mid, i)],
+                "Binary Insertion",
+            );
+            if input[i] < input[mid] {
+                right = mid;
+            } else {
+                left = mid + 1;
+            }
+        }
+        for j in (left..i).rev() {
+            input.swap(j, j + 1);
+            state.record(
+                input,
+                SortPointer(j, i),
+                vec![Operation::Swap(j, j + 1)],
+                "Binary Insertion",
+            );
+        }
+    }
+}
+
+/// Merges runs on the stack until their lengths satisfy Timsort's
+/// invariants, keeping merges balanced.
+fn merge_collapse(input: &mut [f64], state: &mut TimSortState) {
+    while state.runs.frames.len() > 1 {
+        let runs = &state.runs;
+        let mut n = runs.frames.len() - 2;
+        if (n > 0 && run_length(runs, n - 1) <= run_length(runs, n) + run_length(runs, n + 1))
+            || (n > 1 && run_length(runs, n - 2) <= run_length(runs, n - 1) + run_length(runs, n))
+        {
+            if run_length(runs, n - 1) < run_length(runs, n + 1) {
+                n -= 1;
+            }
+        } else if run_length(runs, n) > run_length(runs, n + 1) {
+            break;
+        }
+        merge_at(input, n, state);
+    }
+}
+
+/// Merges the runs at `index` and `index + 1` on the stack.
+fn merge_at(input: &mut [f64], index: usize, state: &mut TimSortState) {
+    let (lo, mid) = (state.runs.frames[index].lo, state.runs.frames[index].hi);
+    let hi = state.runs.frames[index + 1].hi;
+    state.runs.frames[index].hi = hi;
+    state.runs.frames.remove(index + 1);
+
+    // Elements of the left run that are no bigger than the right run's first
+    // element, and elements of the right run no smaller than the left run's
+    // last element, are already in place.
+    let key = Key::at(input, mid);
+    let lo = lo
+        + gallop(
+            &input[lo..mid],
+            Cell::array(lo),
+            key,
+            true,
+            false,
+            input,
+            state,
+        );
+    if lo == mid {
+        return;
+    }
+    let key = Key::at(input, mid - 1);
+    let hi = mid
+        + gallop(
+            &input[mid..hi],
+            Cell::array(mid),
+            key,
+            false,
+            true,
+            input,
+            state,
+        );
+    if hi == mid {
+        return;
+    }
+    // Only the shorter run is copied into the buffer
+    if mid - lo <= hi - mid {
+        merge_lo(input, lo, mid, hi, state);
+    } else {
+        merge_hi(input, lo, mid, hi, state);
+    }
+}
+
+/// A slot in the array, or in the merge buffer, whose slots are numbered
+/// like the array's.
+#[derive(Clone, Copy)]
+struct Cell {
+    index: usize,
+    buffered: bool,
+}
+
+impl Cell {
+    fn array(index: usize) -> Cell {
+        Cell {
+            index,
+            buffered: false,
+        }
+    }
+
+    fn buffer(index: usize) -> Cell {
+        Cell {
+            index,
+            buffered: true,
+        }
+    }
+}
+
+/// A value being galloped towards, and the cell it was read from.
+#[derive(Clone, Copy)]
+struct Key {
+    value: f64,
+    cell: Cell,
+}
+
+impl Key {
+    fn at(values: &[f64], index: usize) -> Key {
+        Key {
+            value: values[index],
+            cell: Cell::array(index),
+        }
+    }
+}
+
+/// Returns how many of the sorted `values` come before `key`, probing at
+/// exponentially growing offsets from the first value, or from the last one
+/// with `from_end`, and then binary searching. With `right`, values equal to
+/// `key` count as coming before it. `start` is the cell `values[0]` was
+/// taken from.
+fn gallop(
+    values: &[f64],
+    start: Cell,
+    key: Key,
+    right: bool,
+    from_end: bool,
+    input: &[f64],
+    state: &mut TimSortState,
+) -> usize {
+    let before = |x: f64| if right { x <= key.value } else { x < key.value };
+    let probe = |index: usize, state: &mut TimSortState| {
+        let probe = Cell {
+            index: start.index + index,
+            ..start
+        };
+        // Once the merge has started writing over the array, a buffered
+        // value is shown in the buffer rather than at its old slot
+        let (pointer, read) = match (key.cell.buffered, probe.buffered) {
+            (true, _) => (SortPointer(probe.index, probe.index), Some(key.cell.index)),
+            (_, true) => (
+                SortPointer(key.cell.index, key.cell.index),
+                Some(probe.index),
+            ),
+            _ => (SortPointer(key.cell.index, probe.index), None),
+        };
+        state.buffer.read = read;
+        state.record(
+            input,
+            pointer,
+            vec![Operation::Compare(key.cell.index, probe.index)],
+            "Gallop",
+        );
+        before(values[index])
+    };
+
+    let n = values.len();
+    let (mut lo, mut hi) = (0, n);
+    let (mut last, mut offset) = (0, 1);
+    if from_end {
+        // values[n - last..] are known to come after the key
+        while offset <= n {
+            if probe(n - offset, state) {
+                lo = n - offset + 1;
+                break;
+            }
+            last = offset;
+            offset *= 2;
+        }
+        hi = n - last;
+    } else {
+        // values[..last] are known to come before the key
+        while offset <= n {
+            if !probe(offset - 1, state) {
+                hi = offset - 1;
+                break;
+            }
+            last = offset;
+            offset *= 2;
+        }
+        lo = last;
+    }
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if probe(mid, state) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    state.buffer.read = None;
+    lo
+}
+
+/// Lowers the gallop threshold while galloping keeps paying off, so it is
+/// quicker to return to, and raises it once galloping stops.
+fn adapt_min_gallop(state: &mut TimSortState, galloping: bool) {
+    if galloping {
+        state.min_gallop = state.min_gallop.saturating_sub(1).max(1);
+    } else {
+        state.min_gallop += 1;
+    }
+}
+
+/// Merges `lo..mid` and `mid..hi` from the low end by copying the left run
+/// into the buffer. Once one run wins `min_gallop` times in a row, the merge
+/// gallops through both runs to copy whole stretches at once, until neither
+/// stretch is at least `MIN_GALLOP` long.
+fn merge_lo(input: &mut [f64], lo: usize, mid: usize, hi: usize, state: &mut TimSortState) {
+    for k in lo..mid {
+        state.buffer.set(k, input[k]);
+        state.record(
+            input,
+            SortPointer(k, k),
+            vec![Operation::AuxWrite(k)],
+            "Merge",
+        );
+    }
+    state.buffer.write = None;
+    let left: Vec<f64> = input[lo..mid].to_vec();
+
+    let (mut i, mut j, mut k) = (0, mid, lo);
+    'merge: while i < left.len() && j < hi {
+        let (mut left_wins, mut right_wins) = (0, 0);
+        while i < left.len() && j < hi {
+            let compare = Operation::Compare(lo + i, j);
+            if input[j] < left[i] {
+                input[k] = input[j];
+                j += 1;
+                right_wins += 1;
+                left_wins = 0;
+            } else {
+                input[k] = left[i];
+                i += 1;
+                left_wins += 1;
+                right_wins = 0;
+            }
+            state.record(
+                input,
+                SortPointer(k, j.min(hi - 1)),
+                vec![compare, Operation::Write(k)],
+                "Merge",
+            );
+            k += 1;
+            if left_wins >= state.min_gallop || right_wins >= state.min_gallop {
+                break;
+            }
+        }
+
+        while i < left.len() && j < hi {
+            adapt_min_gallop(state, true);
+            // Everything in the buffered run up to input[j]
+            let key = Key::at(input, j);
+            let left_count = gallop(
+                &left[i..],
+                Cell::buffer(lo + i),
+                key,
+                true,
+                false,
+                input,
+                state,
+            );
+            for value in &left[i..i + left_count] {
+                input[k] = *value;
+                state.record(
+                    input,
+                    SortPointer(k, j),
+                    vec![Operation::Write(k)],
+                    "Gallop",
+                );
+                k += 1;
+            }
+            i += left_count;
+            if i == left.len() {
+                break 'merge;
+            }
+            // Everything in the right run below left[i]
+            let key = Key {
+                value: left[i],
+                cell: Cell::buffer(lo + i),
+            };
+            let right_count = gallop(
+                &input[j..hi],
+                Cell::array(j),
+                key,
+                false,
+                false,
+                input,
+                state,
+            );
+            for _ in 0..right_count {
+                input[k] = input[j];
+                state.record(
+                    input,
+                    SortPointer(k, j),
+                    vec![Operation::Write(k)],
+                    "Gallop",
+                );
+                k += 1;
+                j += 1;
+            }
+            if left_count < MIN_GALLOP && right_count < MIN_GALLOP {
+                break;
+            }
+        }
+        adapt_min_gallop(state, false);
+    }
+
+    for value in &left[i..] {
+        input[k] = *value;
+        state.record(input, SortPointer(k, k), vec![Operation::Write(k)], "Merge");
+        k += 1;
+    }
+}
+
+/// The mirror image of `merge_lo`, used when the right run is shorter: the
+/// right run is copied into the buffer and the merge fills `lo..hi` from the
+/// high end down.
+fn merge_hi(input: &mut [f64], lo: usize, mid: usize, hi: usize, state: &mut TimSortState) {
+    for k in mid..hi {
+        state.buffer.set(k, input[k]);
+        state.record(
+            input,
+            SortPointer(k, k),
+            vec![Operation::AuxWrite(k)],
+            "Merge",
+        );
+    }
+    state.buffer.write = None;
+    let right: Vec<f64> = input[mid..hi].to_vec();
+
+    // The runs left to merge are input[lo..i] and right[..j], and everything
+    // from k up is in place
+    let (mut i, mut j, mut k) = (mid, right.len(), hi);
+    'merge: while i > lo && j > 0 {
+        let (mut left_wins, mut right_wins) = (0, 0);
+        while i > lo && j > 0 {
+            let compare = Operation::Compare(i - 1, mid + j - 1);
+            k -= 1;
+            if right[j - 1] < input[i - 1] {
+                input[k] = input[i - 1];
+                i -= 1;
+                left_wins += 1;
+                right_wins = 0;
+            } else {
+                input[k] = right[j - 1];
+                j -= 1;
+                right_wins += 1;
+                left_wins = 0;
+            }
+            state.record(
+                input,
+                SortPointer(k, i.saturating_sub(1).max(lo)),
+                vec![compare, Operation::Write(k)],
+                "Merge",
+            );
+            if left_wins >= state.min_gallop || right_wins >= state.min_gallop {
+                break;
+            }
+        }
+
+        while i > lo && j > 0 {
+            adapt_min_gallop(state, true);
+            // Everything in the left run above the buffered run's last value
+            let key = Key {
+                value: right[j - 1],
+                cell: Cell::buffer(mid + j - 1),
+            };
+            let before = gallop(
+                &input[lo..i],
+                Cell::array(lo),
+                key,
+                true,
+                true,
+                input,
+                state,
+            );
+            let left_count = i - lo - before;
+            for _ in 0..left_count {
+                k -= 1;
+                i -= 1;
+                input[k] = input[i];
+                state.record(
+                    input,
+                    SortPointer(k, i),
+                    vec![Operation::Write(k)],
+                    "Gallop",
+                );
+            }
+            if i == lo {
+                break 'merge;
+            }
+            // Everything in the buffered run no smaller than input[i - 1]
+            let key = Key::at(input, i - 1);
+            let before = gallop(
+                &right[..j],
+                Cell::buffer(mid),
+                key,
+                false,
+                true,
+                input,
+                state,
+            );
+            let right_count = j - before;
+            for _ in 0..right_count {
+                k -= 1;
+                j -= 1;
+                input[k] = right[j];
+                state.record(
+                    input,
+                    SortPointer(k, i - 1),
+                    vec![Operation::Write(k)],
+                    "Gallop",
+                );
+            }
+            if left_count < MIN_GALLOP && right_count < MIN_GALLOP {
+                break;
+            }
+        }
+        adapt_min_gallop(state, false);
+    }
+
+    for value in right[..j].iter().rev() {
+        k -= 1;
+        input[k] = *value;
+        state.record(input, SortPointer(k, k), vec![Operation::Write(k)], "Merge");
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::sort::{generate_random_data, test_util};
+
+    #[test]
+    fn test_final_sort() {
+        let items = generate_random_data(100);
+        let result = create_iterator(&mut items.clone()).last();
+        let snapshot = result.unwrap();
+        let sorted = test_util::is_sorted(&snapshot.items);
+        assert!(sorted);
+    }
+
+    #[test]
+    fn test_merges_and_gallops() {
+        // Long runs of either length on each side exercise both merge
+        // directions and galloping through them
+        let mut inputs = vec![(generate_random_data(200), false)];
+        let mut items: Vec<f64> = (0..60).map(|x| x as f64).collect();
+        items.extend((0..20).map(|x| (x * 8) as f64 + 0.5));
+        inputs.push((items, true));
+        let mut items: Vec<f64> = (0..20).map(|x| (x * 8) as f64 + 0.5).collect();
+        items.extend((0..60).map(|x| x as f64));
+        inputs.push((items, true));
+        for (mut items, gallops) in inputs {
+            let mut sorted = items.clone();
+            sorted.sort_by(f64::total_cmp);
+            let snapshots: Vec<Snapshot> = create_iterator(&mut items).collect();
+            assert_eq!(snapshots.last().unwrap().items, sorted);
+            for snapshot in &snapshots {
+                for operation in &snapshot.operations {
+                    if let Operation::Compare(a, b) = operation {
+                        assert_ne!(a, b);
+                    }
+                }
+            }
+
+            // Only a merge that has started galloping copies whole stretches
+            let galloping = snapshots.iter().any(|snapshot| {
+                snapshot.phase == Some("Gallop")
+                    && matches!(snapshot.operations[..], [Operation::Write(_)])
+            });
+            assert!(galloping || !gallops);
+            // Probes into the buffered run are marked there, not in the array
+            let reads = snapshots
+                .iter()
+                .filter(|snapshot| snapshot.aux[0].read.is_some());
+            assert!(reads.clone().count() > 0 || !gallops);
+            for snapshot in reads {
+                let SortPointer(a, b) = snapshot.pointer;
+                assert_eq!(snapshot.phase, Some("Gallop"));
+                assert_eq!(a, b);
+                assert_ne!(Some(a), snapshot.aux[0].read);
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_run_length() {
+        assert_eq!(min_run_length(12), 12);
+        assert_eq!(min_run_length(47), 12);
+        assert_eq!(min_run_length(64), 8);
+    }
+
+    #[test]
+    fn test_presorted_runs() {
+        let mut items: Vec<f64> = (0..40).map(|x| x as f64).collect();
+        items.extend((0..40).rev().map(|x| x as f64 + 0.5));
+        let result = create_iterator(&mut items).last();
+        let snapshot = result.unwrap();
+        assert!(test_util::is_sorted(&snapshot.items));
+    }
+}
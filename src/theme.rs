@@ -0,0 +1,332 @@
+use tui::style::Color;
+
+/// The colours the interface draws each element state in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Theme {
+    pub name: &'static str,
+    /// Tell states apart by shading and border characters rather than
+    /// colour alone.
+    pub patterns: bool,
+    /// Header and HUD while a sort runs.
+    pub sorting: Color,
+    /// Bars and chart borders while a sort runs.
+    pub sorting_bars: Color,
+    /// Header and HUD once the items are sorted.
+    pub complete: Color,
+    /// Bars and chart borders once the items are sorted.
+    pub complete_bars: Color,
+    /// Header, HUD and bars while a sort is paused.
+    pub idle: Color,
+    pub text: Color,
+    pub background: Color,
+    /// Borders, labels and anything not currently in play.
+    pub label: Color,
+    pub muted: Color,
+    /// The items being compared or swapped, and the menu selection.
+    pub compared: Color,
+    /// The current call, variant, pile or network layer.
+    pub current: Color,
+    pub subrange: Color,
+    pub pivot: Color,
+    pub reversal: Color,
+    /// Auxiliary arrays, scatter dots and the metrics sparkline.
+    pub aux: Color,
+    pub histogram: Color,
+    pub wire: Color,
+    pub footer: Color,
+    pub error: Color,
+    /// The algorithms plotted on the complexity charts, in turn.
+    pub series: [Color; 6],
+}
+
+pub const DARK: Theme = Theme {
+    name: "dark",
+    patterns: false,
+    sorting: Color::Yellow,
+    sorting_bars: Color::LightYellow,
+    complete: Color::Green,
+    complete_bars: Color::LightGreen,
+    idle: Color::Gray,
+    text: Color::White,
+    background: Color::Black,
+    label: Color::Gray,
+    muted: Color::DarkGray,
+    compared: Color::Red,
+    current: Color::LightYellow,
+    subrange: Color::LightCyan,
+    pivot: Color::LightRed,
+    reversal: Color::LightMagenta,
+    aux: Color::LightCyan,
+    histogram: Color::LightMagenta,
+    wire: Color::LightBlue,
+    footer: Color::LightBlue,
+    error: Color::LightRed,
+    series: [
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+    ],
+};
+
+/// Darker colours that stay readable on a light background.
+pub const LIGHT: Theme = Theme {
+    name: "light",
+    patterns: false,
+    sorting: Color::Blue,
+    sorting_bars: Color::Blue,
+    complete: Color::Green,
+    complete_bars: Color::Green,
+    idle: Color::DarkGray,
+    text: Color::Black,
+    background: Color::White,
+    label: Color::DarkGray,
+    muted: Color::Gray,
+    compared: Color::Red,
+    current: Color::Rgb(150, 100, 0),
+    subrange: Color::Cyan,
+    pivot: Color::Rgb(200, 80, 0),
+    reversal: Color::Magenta,
+    aux: Color::Cyan,
+    histogram: Color::Magenta,
+    wire: Color::Blue,
+    footer: Color::Blue,
+    error: Color::Red,
+    series: [
+        Color::Red,
+        Color::Green,
+        Color::Rgb(150, 100, 0),
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+    ],
+};
+
+/// The Okabe-Ito palette, which never relies on telling red from green.
+pub const DEUTERANOPIA: Theme = Theme {
+    name: "deuteranopia",
+    patterns: false,
+    sorting: Color::Rgb(230, 159, 0),
+    sorting_bars: Color::Rgb(240, 228, 66),
+    complete: Color::Rgb(0, 114, 178),
+    complete_bars: Color::Rgb(86, 180, 233),
+    idle: Color::Gray,
+    text: Color::White,
+    background: Color::Black,
+    label: Color::Gray,
+    muted: Color::DarkGray,
+    compared: Color::Rgb(213, 94, 0),
+    current: Color::Rgb(240, 228, 66),
+    subrange: Color::Rgb(86, 180, 233),
+    pivot: Color::Rgb(230, 159, 0),
+    reversal: Color::Rgb(204, 121, 167),
+    aux: Color::Rgb(86, 180, 233),
+    histogram: Color::Rgb(204, 121, 167),
+    wire: Color::Rgb(0, 114, 178),
+    footer: Color::Rgb(86, 180, 233),
+    error: Color::Rgb(213, 94, 0),
+    series: [
+        Color::Rgb(230, 159, 0),
+        Color::Rgb(86, 180, 233),
+        Color::Rgb(0, 158, 115),
+        Color::Rgb(240, 228, 66),
+        Color::Rgb(0, 114, 178),
+        Color::Rgb(204, 121, 167),
+    ],
+};
+
+pub const HIGH_CONTRAST: Theme = Theme {
+    name: "high-contrast",
+    patterns: false,
+    sorting: Color::Yellow,
+    sorting_bars: Color::Yellow,
+    complete: Color::LightGreen,
+    complete_bars: Color::LightGreen,
+    idle: Color::White,
+    text: Color::White,
+    background: Color::Black,
+    label: Color::White,
+    muted: Color::Gray,
+    compared: Color::LightRed,
+    current: Color::LightYellow,
+    subrange: Color::LightCyan,
+    pivot: Color::LightMagenta,
+    reversal: Color::LightBlue,
+    aux: Color::LightCyan,
+    histogram: Color::LightMagenta,
+    wire: Color::White,
+    footer: Color::White,
+    error: Color::LightRed,
+    series: [
+        Color::LightRed,
+        Color::LightGreen,
+        Color::Yellow,
+        Color::LightCyan,
+        Color::LightMagenta,
+        Color::White,
+    ],
+};
+
+/// The terminal's own colours, with states shown by patterns alone.
+pub const MONOCHROME: Theme = Theme {
+    name: "monochrome",
+    patterns: true,
+    sorting: Color::Reset,
+    sorting_bars: Color::Reset,
+    complete: Color::Reset,
+    complete_bars: Color::Reset,
+    idle: Color::Reset,
+    text: Color::Reset,
+    background: Color::Reset,
+    label: Color::Reset,
+    muted: Color::Reset,
+    compared: Color::Reset,
+    current: Color::Reset,
+    subrange: Color::Reset,
+    pivot: Color::Reset,
+    reversal: Color::Reset,
+    aux: Color::Reset,
+    histogram: Color::Reset,
+    wire: Color::Reset,
+    footer: Color::Reset,
+    error: Color::Reset,
+    series: [Color::Reset; 6],
+};
+
+impl Default for Theme {
+    fn default() -> Self {
+        DARK
+    }
+}
+
+impl Theme {
+    pub const PRESETS: [Theme; 5] = [DARK, LIGHT, DEUTERANOPIA, HIGH_CONTRAST, MONOCHROME];
+
+    pub fn find(name: &str) -> Option<Theme> {
+        Theme::PRESETS.into_iter().find(|theme| theme.name == name)
+    }
+
+    /// The names the elements go by in the config file.
+    pub const ELEMENTS: [&'static str; 19] = [
+        "sorting",
+        "sorting_bars",
+        "complete",
+        "complete_bars",
+        "idle",
+        "text",
+        "background",
+        "label",
+        "muted",
+        "compared",
+        "current",
+        "subrange",
+        "pivot",
+        "reversal",
+        "aux",
+        "histogram",
+        "wire",
+        "footer",
+        "error",
+    ];
+
+    /// The colour of the element called `name`, if there is one.
+    pub fn element_mut(&mut self, name: &str) -> Option<&mut Color> {
+        Some(match name {
+            "sorting" => &mut self.sorting,
+            "sorting_bars" => &mut self.sorting_bars,
+            "complete" => &mut self.complete,
+            "complete_bars" => &mut self.complete_bars,
+            "idle" => &mut self.idle,
+            "text" => &mut self.text,
+            "background" => &mut self.background,
+            "label" => &mut self.label,
+            "muted" => &mut self.muted,
+            "compared" => &mut self.compared,
+            "current" => &mut self.current,
+            "subrange" => &mut self.subrange,
+            "pivot" => &mut self.pivot,
+            "reversal" => &mut self.reversal,
+            "aux" => &mut self.aux,
+            "histogram" => &mut self.histogram,
+            "wire" => &mut self.wire,
+            "footer" => &mut self.footer,
+            "error" => &mut self.error,
+            _ => return None,
+        })
+    }
+}
+
+/// Parses a colour name such as `light_blue`, or a `#rrggbb` hex code.
+pub fn parse_color(text: &str) -> Option<Color> {
+    if let Some(hex) = text.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+    Some(
+        match text.to_lowercase().replace(['-', ' '], "_").as_str() {
+            "reset" => Color::Reset,
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "gray" | "grey" => Color::Gray,
+            "dark_gray" | "dark_grey" => Color::DarkGray,
+            "light_red" => Color::LightRed,
+            "light_green" => Color::LightGreen,
+            "light_yellow" => Color::LightYellow,
+            "light_blue" => Color::LightBlue,
+            "light_magenta" => Color::LightMagenta,
+            "light_cyan" => Color::LightCyan,
+            "white" => Color::White,
+            _ => return None,
+        },
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("light_blue"), Some(Color::LightBlue));
+        assert_eq!(parse_color("Dark-Grey"), Some(Color::DarkGray));
+        assert_eq!(parse_color("#ff8000"), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(parse_color("#ff80"), None);
+        assert_eq!(parse_color("#gg0000"), None);
+        assert_eq!(parse_color("mauve"), None);
+    }
+
+    #[test]
+    fn test_elements() {
+        let mut theme = Theme::default();
+        for name in Theme::ELEMENTS {
+            *theme.element_mut(name).unwrap() = Color::Blue;
+        }
+        assert_eq!(theme.footer, Color::Blue);
+        assert_eq!(theme.sorting, Color::Blue);
+        assert!(theme.element_mut("border").is_none());
+    }
+
+    #[test]
+    fn test_presets() {
+        for preset in Theme::PRESETS {
+            assert_eq!(Theme::find(preset.name), Some(preset));
+            if !preset.patterns {
+                for (i, color) in preset.series.iter().enumerate() {
+                    assert!(!preset.series[i + 1..].contains(color));
+                }
+            }
+        }
+        assert_eq!(Theme::default(), DARK);
+        assert!(Theme::find("solarized").is_none());
+    }
+}
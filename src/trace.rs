@@ -0,0 +1,242 @@
+use crate::sort::{Operation, Sort};
+use std::{error::Error, fmt, str::FromStr};
+
+/// A recorded run of a sort: the input it started on and, for every step,
+/// the pointer, the operations performed and the array afterwards.
+///
+/// Traces are written as plain text, one keyword per line:
+///
+/// ```text
+/// term-sort trace 1
+/// name Bubble Sort
+/// input 3 1 2
+/// step 0 1
+/// ops cmp 0 1; swp 0 1
+/// items 1 3 2
+/// ```
+///
+/// `phase` lines may follow a `step` line, and `ops` is left out of steps
+/// that perform no operations.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Trace {
+    pub name: String,
+    pub input: Vec<f64>,
+    pub steps: Vec<TraceStep>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TraceStep {
+    pub pointer: (usize, usize),
+    pub operations: Vec<Operation>,
+    pub phase: Option<String>,
+    pub items: Vec<f64>,
+}
+
+const HEADER: &str = "term-sort trace 1";
+
+impl Trace {
+    /// Runs `sort` from the start of its input to the end, recording every
+    /// step. The sort is left complete.
+    pub fn record(sort: &mut dyn Sort) -> Trace {
+        let input = sort.stepper().input.clone();
+        sort.reset(input.clone());
+        let mut steps = vec![];
+        loop {
+            sort.step();
+            if sort.is_sorted() {
+                break;
+            }
+            let snapshot = sort.get_snapshot();
+            steps.push(TraceStep {
+                pointer: (snapshot.pointer.0, snapshot.pointer.1),
+                operations: snapshot.operations.clone(),
+                phase: snapshot.phase.map(str::to_string),
+                items: snapshot.items.clone(),
+            });
+        }
+        Trace {
+            name: sort.get_name(),
+            input,
+            steps,
+        }
+    }
+}
+
+fn write_values(f: &mut fmt::Formatter, keyword: &str, values: &[f64]) -> fmt::Result {
+    write!(f, "{}", keyword)?;
+    for value in values {
+        write!(f, " {}", value)?;
+    }
+    writeln!(f)
+}
+
+fn format_operation(operation: &Operation) -> String {
+    match operation {
+        Operation::Compare(a, b) => format!("cmp {} {}", a, b),
+        Operation::Swap(a, b) => format!("swp {} {}", a, b),
+        Operation::Write(index) => format!("wr {}", index),
+        Operation::Read(index) => format!("rd {}", index),
+        Operation::AuxWrite(index) => format!("aux {}", index),
+        Operation::Distribute { index, bucket } => format!("dist {} {}", index, bucket),
+        Operation::Reverse(lo, hi) => format!("rev {} {}", lo, hi),
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        writeln!(f, "name {}", self.name)?;
+        write_values(f, "input", &self.input)?;
+        for step in &self.steps {
+            writeln!(f, "step {} {}", step.pointer.0, step.pointer.1)?;
+            if let Some(phase) = &step.phase {
+                writeln!(f, "phase {}", phase)?;
+            }
+            if !step.operations.is_empty() {
+                let operations: Vec<String> =
+                    step.operations.iter().map(format_operation).collect();
+                writeln!(f, "ops {}", operations.join("; "))?;
+            }
+            write_values(f, "items", &step.items)?;
+        }
+        Ok(())
+    }
+}
+
+/// Why a trace could not be read, and on which line (counting from 1).
+#[derive(Debug, PartialEq)]
+pub struct ParseTraceError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseTraceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseTraceError {}
+
+fn parse_numbers<T: FromStr>(text: &str) -> Result<Vec<T>, String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.parse()
+                .map_err(|_| format!("`{}` is not a number", word))
+        })
+        .collect()
+}
+
+fn parse_operation(text: &str) -> Result<Operation, String> {
+    let (name, arguments) = text.trim().split_once(' ').unwrap_or((text.trim(), ""));
+    let arguments: Vec<usize> = parse_numbers(arguments)?;
+    let operation = match (name, arguments.as_slice()) {
+        ("cmp", [a, b]) => Operation::Compare(*a, *b),
+        ("swp", [a, b]) => Operation::Swap(*a, *b),
+        ("wr", [index]) => Operation::Write(*index),
+        ("rd", [index]) => Operation::Read(*index),
+        ("aux", [index]) => Operation::AuxWrite(*index),
+        ("dist", [index, bucket]) => Operation::Distribute {
+            index: *index,
+            bucket: *bucket,
+        },
+        ("rev", [lo, hi]) => Operation::Reverse(*lo, *hi),
+        _ => return Err(format!("unknown operation `{}`", text.trim())),
+    };
+    Ok(operation)
+}
+
+impl FromStr for Trace {
+    type Err = ParseTraceError;
+
+    fn from_str(text: &str) -> Result<Trace, ParseTraceError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        match lines.next() {
+            Some((_, line)) if line.trim() == HEADER => (),
+            other => {
+                return Err(ParseTraceError {
+                    line: other.map_or(1, |(index, _)| index + 1),
+                    message: format!("expected `{}`", HEADER),
+                })
+            }
+        }
+
+        let mut trace = Trace {
+            name: String::new(),
+            input: vec![],
+            steps: vec![],
+        };
+        for (index, line) in lines {
+            let error = |message: String| ParseTraceError {
+                line: index + 1,
+                message,
+            };
+            let (keyword, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+            let step = trace.steps.last_mut();
+            match (keyword, step) {
+                ("name", _) => trace.name = rest.to_string(),
+                ("input", _) => trace.input = parse_numbers(rest).map_err(error)?,
+                ("step", _) => match parse_numbers::<usize>(rest).map_err(error)?[..] {
+                    [a, b] => trace.steps.push(TraceStep {
+                        pointer: (a, b),
+                        operations: vec![],
+                        phase: None,
+                        items: vec![],
+                    }),
+                    _ => return Err(error("expected two pointer indices".to_string())),
+                },
+                ("phase", Some(step)) => step.phase = Some(rest.to_string()),
+                ("ops", Some(step)) => {
+                    step.operations = rest
+                        .split(';')
+                        .map(parse_operation)
+                        .collect::<Result<_, _>>()
+                        .map_err(error)?
+                }
+                ("items", Some(step)) => step.items = parse_numbers(rest).map_err(error)?,
+                ("phase" | "ops" | "items", None) => {
+                    return Err(error(format!("`{}` before the first step", keyword)))
+                }
+                _ => return Err(error(format!("unknown keyword `{}`", keyword))),
+            }
+        }
+        Ok(trace)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_round_trip() {
+        let mut sort = registry::find("bubble")
+            .unwrap()
+            .create(vec![3.0, 1.0, 2.0]);
+        let trace = Trace::record(sort.as_mut());
+        assert_eq!(trace.steps.len(), 3);
+        assert_eq!(
+            trace.steps[0].operations,
+            vec![Operation::Compare(0, 1), Operation::Swap(0, 1)]
+        );
+        assert_eq!(trace.steps[2].items, vec![1.0, 2.0, 3.0]);
+        assert_eq!(trace.to_string().parse::<Trace>(), Ok(trace));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("name x".parse::<Trace>().unwrap_err().line, 1);
+        let text = format!("{}\nstep 0 1\nops cmp 0\n", HEADER);
+        assert_eq!(
+            text.parse::<Trace>(),
+            Err(ParseTraceError {
+                line: 3,
+                message: "unknown operation `cmp 0`".to_string()
+            })
+        );
+    }
+}
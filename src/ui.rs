@@ -1,30 +1,49 @@
-use crate::app::{App, View};
-use crate::sort;
+use crate::app::{App, RenderStyle, SettingsField, View};
+use crate::keymap::{self, Action, Keymap};
+use crate::theme::Theme;
+use term_sort::complexity;
+use term_sort::metrics::{Metrics, MetricsHistory};
+use term_sort::sort;
 use tui::{
     backend::Backend,
+    buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Span, Spans},
-    widgets::{BarChart, Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{
+        canvas::{Canvas, Points},
+        Axis, BarChart, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, List,
+        ListItem, ListState, Paragraph, Sparkline, Widget, Wrap,
+    },
     Frame,
 };
 
 pub fn draw_header(
     f: &mut Frame<impl Backend>,
     chunk: Rect,
+    theme: &Theme,
     title: String,
     sorting: bool,
     complete: bool,
 ) {
+    let title = match (theme.patterns, sorting, complete) {
+        (true, true, _) => format!("▶ {} ▶", title),
+        (true, false, true) => format!("✔ {} ✔", title),
+        _ => title,
+    };
     let text = vec![Spans::from(vec![Span::raw(title)])];
 
-    let p_style = if sorting {
-        Style::default().bg(Color::Yellow).fg(Color::Black)
+    let mut p_style = if sorting {
+        Style::default().bg(theme.sorting).fg(theme.background)
     } else if complete {
-        Style::default().bg(Color::Green).fg(Color::Black)
+        Style::default().bg(theme.complete).fg(theme.background)
     } else {
-        Style::default().bg(Color::Black).fg(Color::White)
+        Style::default().bg(theme.background).fg(theme.text)
     };
+    if theme.patterns && (sorting || complete) {
+        p_style = p_style.add_modifier(Modifier::REVERSED);
+    }
 
     let block = Block::default();
     let paragraph = Paragraph::new(text)
@@ -36,25 +55,56 @@ pub fn draw_header(
     f.render_widget(paragraph, chunk);
 }
 
+/// Under a patterned theme running and finished sorts are framed by heavier
+/// borders, as their colours may not tell them apart.
+fn state_border(theme: &Theme, sorting: bool, complete: bool) -> BorderType {
+    match (theme.patterns, sorting, complete) {
+        (true, true, _) => BorderType::Thick,
+        (true, false, true) => BorderType::Double,
+        _ => BorderType::Plain,
+    }
+}
+
 pub fn draw_menu_list(
     f: &mut Frame<impl Backend>,
     chunk: Rect,
-    items: &Vec<(&'static str, View)>,
-    state: &mut ListState,
+    theme: &Theme,
+    items: &[(&'static str, View)],
+    selected: Option<usize>,
+    offset: &mut usize,
 ) {
-    let items: Vec<ListItem> = items.iter().map(|(text, _)| ListItem::new(*text)).collect();
+    // Scroll just far enough to keep the selection in view. The offset is
+    // tracked here rather than left to the list, so clicks can be matched to
+    // the entries under them.
+    let height = chunk.height as usize;
+    *offset = (*offset).min(items.len().saturating_sub(height));
+    if let Some(selected) = selected {
+        if selected < *offset {
+            *offset = selected;
+        } else if selected >= *offset + height {
+            *offset = (selected + 1).saturating_sub(height);
+        }
+    }
+    let mut state = ListState::default();
+    state.select(selected.and_then(|selected| selected.checked_sub(*offset)));
+    let items: Vec<ListItem> = items
+        .iter()
+        .skip(*offset)
+        .take(height)
+        .map(|(text, _)| ListItem::new(*text))
+        .collect();
 
     let list = List::new(items)
         .block(Block::default())
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .highlight_style(
             Style::default()
-                .fg(Color::Red)
+                .fg(theme.compared)
                 .add_modifier(Modifier::ITALIC),
         )
         .highlight_symbol(">>");
 
-    f.render_stateful_widget(list, chunk, state);
+    f.render_stateful_widget(list, chunk, &mut state);
 }
 
 pub fn draw_menu(f: &mut Frame<impl Backend>, app: &mut App, chunk: Rect) {
@@ -72,43 +122,150 @@ pub fn draw_menu(f: &mut Frame<impl Backend>, app: &mut App, chunk: Rect) {
         )
         .split(chunk);
 
-    draw_header(f, chunks[0], "term-sort".to_string(), false, false);
+    let theme = &app.theme;
+    draw_header(f, chunks[0], theme, "term-sort".to_string(), false, false);
 
     let menu = app.states.menu.as_mut().unwrap();
 
-    draw_menu_list(f, chunks[2], menu.list.items.as_ref(), &mut menu.list.state);
-    draw_menu_footer(f, chunks[3]);
+    let arrangement = format!(
+        "filter: {}, sorted by: {}, theme: {}",
+        menu.filter.map_or("none", |property| property.get_name()),
+        menu.order.get_name(),
+        theme.name
+    );
+    f.render_widget(
+        Paragraph::new(arrangement).style(Style::default().fg(theme.label)),
+        chunks[1],
+    );
+
+    let selected = menu.selected();
+    let list_chunk = if menu.show_info {
+        let body = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(INFO_WIDTH)].as_ref())
+            .split(chunks[2]);
+        draw_info(f, body[1], theme, selected);
+        body[0]
+    } else {
+        chunks[2]
+    };
+
+    draw_menu_list(
+        f,
+        list_chunk,
+        theme,
+        &menu.list.items,
+        menu.list.state.selected(),
+        &mut menu.offset,
+    );
+    app.areas.menu = list_chunk;
+    draw_menu_footer(f, chunks[3], theme, &app.keymap);
+}
+
+const INFO_WIDTH: u16 = 36;
+
+/// Shows the complexities and properties of the highlighted menu entry.
+pub fn draw_info(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    selected: Option<(&'static str, View)>,
+) {
+    let block = Block::default()
+        .title("Info")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.label));
+    let info = selected.and_then(|(_, view)| view.info());
+    let text = match (selected, info) {
+        (Some((name, _)), Some(info)) => {
+            let row = |label: &str, value: &str| {
+                Spans::from(vec![
+                    Span::styled(format!("{:<10}", label), Style::default().fg(theme.label)),
+                    Span::raw(value.to_string()),
+                ])
+            };
+            let flag = |label: &str, value: bool| {
+                let (text, color) = if value {
+                    ("yes", theme.complete)
+                } else {
+                    ("no", theme.compared)
+                };
+                Spans::from(vec![
+                    Span::styled(format!("{:<10}", label), Style::default().fg(theme.label)),
+                    Span::styled(text, Style::default().fg(color)),
+                ])
+            };
+            vec![
+                Spans::from(Span::styled(
+                    name,
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Spans::default(),
+                row(
+                    "category",
+                    match selected {
+                        Some((_, View::Sort(algorithm))) => algorithm.category.get_name(),
+                        _ => "",
+                    },
+                ),
+                row("best", info.best.get_name()),
+                row("average", info.average.get_name()),
+                row("worst", info.worst.get_name()),
+                row("space", info.space.get_name()),
+                Spans::default(),
+                flag("stable", info.stable),
+                flag("in-place", info.in_place),
+                flag("adaptive", info.adaptive),
+                flag("online", info.online),
+            ]
+        }
+        _ => vec![Spans::from("No algorithm selected")],
+    };
+
+    f.render_widget(Paragraph::new(text).block(block), chunk);
 }
 
 pub fn draw_sort_hud(
     f: &mut Frame<impl Backend>,
     chunk: Rect,
+    theme: &Theme,
     sort_name: &str,
-    current_step: &str,
-    sorting: bool,
-    complete: bool,
+    sort_iter: &dyn sort::Sort,
 ) {
+    let sorting = sort_iter.is_active();
+    let complete = sort_iter.is_sorted();
+    let counters = sort_iter.counters();
+    let operations_width = chunk.width * 38 / 100;
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .margin(0)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .constraints(
+            [
+                Constraint::Length(chunk.width.saturating_sub(operations_width + 28)),
+                Constraint::Length(14),
+                Constraint::Length(operations_width),
+                Constraint::Length(14),
+            ]
+            .as_ref(),
+        )
         .split(chunk);
 
     let p_style = if sorting {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.sorting)
     } else if complete {
-        Style::default().fg(Color::Green)
+        Style::default().fg(theme.complete)
     } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(theme.idle)
     };
 
     let border_style = if sorting {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.sorting)
     } else if complete {
-        Style::default().fg(Color::Green)
+        Style::default().fg(theme.complete)
     } else {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme.text)
     };
+    let border_type = state_border(theme, sorting, complete);
 
     // Left block
     let text = vec![Spans::from(vec![Span::raw(sort_name)])];
@@ -116,7 +273,8 @@ pub fn draw_sort_hud(
     let block = Block::default()
         .title("Sort Name")
         .borders(Borders::ALL)
-        .border_style(border_style);
+        .border_style(border_style)
+        .border_type(border_type);
     let paragraph = Paragraph::new(text)
         .block(block)
         .style(p_style)
@@ -125,13 +283,16 @@ pub fn draw_sort_hud(
 
     f.render_widget(paragraph, chunks[0]);
 
-    // Right block
-    let text = vec![Spans::from(vec![Span::raw(current_step)])];
+    // Middle block
+    let text = vec![Spans::from(vec![Span::raw(
+        sort_iter.get_current_step().to_string(),
+    )])];
 
     let block = Block::default()
         .title("Current Step")
         .borders(Borders::ALL)
-        .border_style(border_style);
+        .border_style(border_style)
+        .border_type(border_type);
     let paragraph = Paragraph::new(text)
         .block(block)
         .style(p_style)
@@ -139,110 +300,1840 @@ pub fn draw_sort_hud(
         .wrap(Wrap { trim: true });
 
     f.render_widget(paragraph, chunks[1]);
+
+    // Right block
+    let text = vec![Spans::from(vec![Span::raw(format_counters(counters))])];
+
+    let block = Block::default()
+        .title("Operations")
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .border_type(border_type);
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .style(p_style)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, chunks[2]);
+
+    // Extra space, in elements
+    let text = vec![Spans::from(vec![Span::raw(
+        counters.peak_aux_memory.to_string(),
+    )])];
+
+    let block = Block::default()
+        .title("Peak Aux Mem")
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .border_type(border_type);
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .style(p_style)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, chunks[3]);
+}
+
+fn format_counters(counters: &sort::Counters) -> String {
+    let mut text = format!(
+        "cmp {} swp {} wr {}",
+        counters.comparisons,
+        counters.swaps,
+        counters.array_writes()
+    );
+    if counters.aux_writes > 0 {
+        text.push_str(&format!(" aux {}", counters.aux_writes));
+    }
+    if counters.reversals > 0 {
+        text.push_str(&format!(" rev {}", counters.reversals));
+    }
+    if counters.reads > 0 {
+        text.push_str(&format!(" rd {}", counters.reads));
+    }
+    text
 }
 
-pub fn draw_sort(f: &mut Frame<impl Backend>, chunk: Rect, sort_iter: &mut Box<dyn sort::Sort>) {
+pub fn draw_sort(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    sort_iter: &mut Box<dyn sort::Sort>,
+    render_style: RenderStyle,
+    inspected: Option<usize>,
+) -> Option<(Rect, u16)> {
+    // Sorts without a network have no wire diagram to show
+    let render_style = match render_style {
+        RenderStyle::WireDiagram if sort_iter.network().is_none() => RenderStyle::Bars,
+        render_style => render_style,
+    };
+    let aux_arrays = sort_iter.aux_arrays();
+    let mut constraints = vec![Constraint::Length(3), Constraint::Min(0)];
+    constraints.extend(
+        aux_arrays
+            .iter()
+            .map(|_| Constraint::Length(AUX_ARRAY_HEIGHT)),
+    );
+    if aux_arrays.is_empty() && sort_iter.histogram().is_some() {
+        constraints.push(Constraint::Length(AUX_ARRAY_HEIGHT));
+    }
+    if sort_iter.piles().is_some() {
+        constraints.push(Constraint::Length(AUX_ARRAY_HEIGHT));
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
-        .constraints([Constraint::Length(3), Constraint::Percentage(100)].as_ref())
+        .constraints(constraints)
         .split(chunk);
 
-    draw_sort_hud(
-        f,
-        chunks[0],
-        sort_iter.get_name().as_str(),
-        sort_iter.get_current_step().to_string().as_str(),
-        sort_iter.is_active(),
-        sort_iter.is_sorted(),
-    );
+    let mut secondary_chunks = chunks[2..].to_vec();
+    if let Some(piles) = sort_iter.piles() {
+        draw_piles(f, secondary_chunks.pop().unwrap(), theme, piles);
+    }
 
-    let data: Vec<(&'static str, u64)> = sort_iter
-        .items()
-        .iter()
-        .enumerate()
-        .map(|(i, x)| {
-            let p = sort_iter.get_pointer();
-            if sort_iter.is_sorted() == true
-                || sort_iter.is_sorted() == false && (p.0 != i && p.1 != i)
-            {
-                ("", *x as u64)
-            } else {
-                ("⬆", *x as u64)
-            }
-        })
-        .collect();
+    // The histogram shares the last auxiliary row, or takes its own
+    if let Some(histogram) = sort_iter.histogram() {
+        let last = secondary_chunks.len() - 1;
+        if !aux_arrays.is_empty() {
+            let split = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)].as_ref())
+                .split(secondary_chunks[last]);
+            secondary_chunks[last] = split[0];
+            draw_histogram(f, split[1], theme, histogram);
+        } else {
+            draw_histogram(f, secondary_chunks[last], theme, histogram);
+        }
+    }
+    for (aux, aux_chunk) in aux_arrays.iter().zip(&secondary_chunks) {
+        draw_aux_array(f, *aux_chunk, theme, aux);
+    }
+
+    let mut details = vec![];
+    if let Some(phase) = sort_iter.phase() {
+        details.push(phase.to_string());
+    }
+    if let Some(gap) = sort_iter.gap() {
+        details.push(format!("gap {}", gap));
+    }
+    let sort_name = if details.is_empty() {
+        sort_iter.get_name()
+    } else {
+        format!("{} ({})", sort_iter.get_name(), details.join(", "))
+    };
+
+    draw_sort_hud(f, chunks[0], theme, sort_name.as_str(), sort_iter.as_ref());
 
     let border_style = if sort_iter.is_active() {
-        Style::default().fg(Color::LightYellow)
+        Style::default().fg(theme.sorting_bars)
     } else if sort_iter.is_sorted() {
-        Style::default().fg(Color::LightGreen)
+        Style::default().fg(theme.complete_bars)
     } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(theme.idle)
     };
 
-    let chart = BarChart::default()
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(border_style),
-        )
-        .data(&data)
-        .bar_width(1)
-        .bar_style(border_style)
-        .label_style(Style::default().fg(Color::Red));
+    let block = Block::default()
+        .title(render_style.get_name())
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .border_type(state_border(
+            theme,
+            sort_iter.is_active(),
+            sort_iter.is_sorted(),
+        ));
+
+    let chart_chunk = if sort_iter.is_recursive() {
+        let chart_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(CALL_STACK_WIDTH)].as_ref())
+            .split(chunks[1]);
+        draw_call_stack(
+            f,
+            chart_chunks[1],
+            theme,
+            sort_iter.call_stack(),
+            border_style,
+        );
+        chart_chunks[0]
+    } else if !sort_iter.variants().is_empty() {
+        let chart_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(VARIANTS_WIDTH)].as_ref())
+            .split(chunks[1]);
+        draw_variants(
+            f,
+            chart_chunks[1],
+            theme,
+            sort_iter.variants(),
+            border_style,
+        );
+        chart_chunks[0]
+    } else {
+        chunks[1]
+    };
+
+    let chart_chunk = match sort_iter.heap_size() {
+        Some(heap_size) => {
+            let levels = usize::BITS - sort_iter.items().len().leading_zeros();
+            let heap_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(levels as u16 + 2)].as_ref())
+                .split(chart_chunk);
+            draw_heap_tree(
+                f,
+                heap_chunks[1],
+                theme,
+                sort_iter.as_ref(),
+                heap_size,
+                border_style,
+            );
+            heap_chunks[0]
+        }
+        None => chart_chunk,
+    };
+
+    // Only bars line up with the items closely enough to be clicked on
+    let sort_iter = sort_iter.as_ref();
+    match render_style {
+        RenderStyle::Bars => {
+            return Some(draw_bars(
+                f,
+                chart_chunk,
+                theme,
+                sort_iter,
+                block,
+                border_style,
+                inspected,
+            ))
+        }
+        RenderStyle::Scatter => draw_scatter(f, chart_chunk, theme, sort_iter, block, border_style),
+        RenderStyle::HueStrip => draw_hue_strip(f, chart_chunk, theme, sort_iter, block),
+        RenderStyle::DisparityCircle => {
+            draw_disparity_circle(f, chart_chunk, theme, sort_iter, block)
+        }
+        RenderStyle::WireDiagram => draw_wire_diagram(f, chart_chunk, theme, sort_iter, block),
+    }
+    None
+}
+
+const CALL_STACK_WIDTH: u16 = 24;
+const VARIANTS_WIDTH: u16 = 26;
+
+/// The columns the panel beside the chart takes from it, if the sort shows
+/// one.
+pub fn side_panel_width(sort_iter: &dyn sort::Sort) -> u16 {
+    if sort_iter.is_recursive() {
+        CALL_STACK_WIDTH
+    } else if !sort_iter.variants().is_empty() {
+        VARIANTS_WIDTH
+    } else {
+        0
+    }
+}
+
+/// Lists the operation totals of every variant of the sort over the current
+/// input, marking the one being run.
+pub fn draw_variants(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    variants: &[sort::Variant],
+    border_style: Style,
+) {
+    let mut text = vec![Spans::from(Span::styled(
+        "  variant      cmp   swp",
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    for variant in variants {
+        let (marker, style) = if variant.current {
+            ("▸", Style::default().fg(theme.current))
+        } else {
+            (" ", Style::default().fg(theme.label))
+        };
+        text.push(Spans::from(Span::styled(
+            format!(
+                "{} {:<10} {:>5} {:>5}",
+                marker, variant.name, variant.counters.comparisons, variant.counters.swaps
+            ),
+            style,
+        )));
+    }
+
+    let block = Block::default()
+        .title("Variants")
+        .borders(Borders::ALL)
+        .border_style(border_style);
 
-    f.render_widget(chart, chunks[1]);
+    f.render_widget(Paragraph::new(text).block(block), chunk);
 }
 
-pub fn draw_menu_footer(f: &mut Frame<impl Backend>, chunk: Rect) {
+pub fn draw_call_stack(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    stack: &sort::CallStack,
+    border_style: Style,
+) {
+    let mut text = vec![
+        Spans::from(Span::raw(format!(
+            "depth {}, peak {}",
+            stack.frames.len(),
+            stack.peak_depth
+        ))),
+        Spans::from(Span::styled(
+            " d  lo..hi  pivot",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+
+    // Innermost frame first, so the active call stays visible
+    for (i, frame) in stack.frames.iter().rev().enumerate() {
+        let pivot = frame
+            .pivot
+            .map_or("-".to_string(), |pivot| pivot.to_string());
+        let style = if i == 0 {
+            Style::default().fg(theme.current)
+        } else {
+            Style::default().fg(theme.label)
+        };
+        text.push(Spans::from(Span::styled(
+            format!(
+                "{:>2} {:>3}..{:<3} {:>5}",
+                frame.depth, frame.lo, frame.hi, pivot
+            ),
+            style,
+        )));
+    }
+
     let block = Block::default()
-        .title("q/Ctrl-c: quit, enter: select sort")
-        .style(Style::default().fg(Color::LightBlue).bg(Color::Reset));
+        .title(stack.name)
+        .borders(Borders::ALL)
+        .border_style(border_style);
 
-    f.render_widget(block, chunk);
+    f.render_widget(Paragraph::new(text).block(block), chunk);
 }
 
-pub fn draw_sort_footer(f: &mut Frame<impl Backend>, chunk: Rect, active: bool, sorted: bool) {
-    let title = if active {
-        "Enter: pause sort, q: back to menu"
-    } else if sorted {
-        "Enter: restart sort, q: back to menu"
+/// A binary heap laid out one level per row, with each node joined to its
+/// children by a bracket running between their columns.
+struct HeapTree<'a> {
+    theme: &'a Theme,
+    items: &'a [f64],
+    heap_size: usize,
+    highlights: [usize; 2],
+}
+
+impl<'a> Widget for HeapTree<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let center = |index: usize| {
+            let level = usize::BITS - (index + 1).leading_zeros() - 1;
+            let slots = 1usize << level;
+            let slot = index + 1 - slots;
+            let x = (slot as f64 + 0.5) * area.width as f64 / slots as f64;
+            (area.x + x as u16, area.y + level as u16)
+        };
+
+        for (i, value) in self.items.iter().enumerate() {
+            let (x, y) = center(i);
+            if y >= area.bottom() {
+                break;
+            }
+            let in_heap = i < self.heap_size;
+            let style = if !in_heap {
+                Style::default().fg(self.theme.muted)
+            } else if self.highlights.contains(&i) {
+                Style::default()
+                    .fg(self.theme.compared)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+
+            // Bracket out to the children that are still part of the heap
+            let label = format!("{:>2}", *value as u64);
+            let label_start = x.saturating_sub(1);
+            let label_end = label_start + label.len() as u16;
+            for (child, corner) in [(2 * i + 1, "╭"), (2 * i + 2, "╮")] {
+                if !in_heap || child >= self.heap_size {
+                    continue;
+                }
+                let (child_x, _) = center(child);
+                let (from, to) = if child_x < x {
+                    (child_x, label_start)
+                } else {
+                    (label_end, child_x + 1)
+                };
+                for line_x in from..to.min(area.right()) {
+                    let symbol = if line_x == child_x { corner } else { "─" };
+                    buf.get_mut(line_x, y)
+                        .set_symbol(symbol)
+                        .set_style(Style::default().fg(self.theme.label));
+                }
+            }
+            buf.set_stringn(
+                label_start,
+                y,
+                label,
+                (area.right() - label_start) as usize,
+                style,
+            );
+        }
+    }
+}
+
+pub fn draw_heap_tree(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    sort_iter: &dyn sort::Sort,
+    heap_size: usize,
+    border_style: Style,
+) {
+    let pointer = sort_iter.get_pointer();
+    let highlights = if sort_iter.is_sorted() {
+        [usize::MAX; 2]
     } else {
-        "Enter: start sort, q: back to menu"
+        [pointer.0, pointer.1]
     };
-
     let block = Block::default()
-        .title(title)
-        .style(Style::default().fg(Color::LightBlue).bg(Color::Reset));
+        .title("Heap")
+        .borders(Borders::ALL)
+        .border_style(border_style);
+    let inner = block.inner(chunk);
 
     f.render_widget(block, chunk);
+    f.render_widget(
+        HeapTree {
+            theme,
+            items: sort_iter.items(),
+            heap_size,
+            highlights,
+        },
+        inner,
+    );
 }
 
-pub fn draw_single_sort(f: &mut Frame<impl Backend>, app: &mut App, chunk: Rect) {
-    if let Some(sort) = app.sort.as_mut() {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(1)
-            .constraints(
-                [
-                    Constraint::Length(1),
-                    Constraint::Length(1),
-                    Constraint::Percentage(90),
-                    Constraint::Length(1),
-                ]
-                .as_ref(),
-            )
-            .split(chunk);
+/// A sorting network drawn with one vertical wire per element, lined up with
+/// the bars, and comparators as horizontal links between wires. Comparators
+/// of a layer that would overlap are spread over several rows.
+struct WireDiagram<'a> {
+    theme: &'a Theme,
+    len: usize,
+    network: &'a sort::Network,
+    /// Layers before this one have been applied.
+    current: usize,
+    sorted: bool,
+    gap: u16,
+}
 
-        draw_header(
-            f,
-            chunks[0],
-            sort.get_name(),
-            sort.is_active(),
-            sort.is_sorted(),
+impl<'a> WireDiagram<'a> {
+    fn rows(&self) -> Vec<(usize, Vec<(usize, usize)>)> {
+        let mut rows: Vec<(usize, Vec<(usize, usize)>)> = vec![];
+        for (layer_index, layer) in self.network.iter().enumerate() {
+            let first_row = rows.len();
+            for &(i, j) in layer {
+                let free = rows[first_row..]
+                    .iter()
+                    .position(|(_, row)| row.iter().all(|&(a, b)| b < i || j < a));
+                match free {
+                    Some(row) => rows[first_row + row].1.push((i, j)),
+                    None => rows.push((layer_index, vec![(i, j)])),
+                }
+            }
+        }
+        rows
+    }
+}
+
+impl<'a> Widget for WireDiagram<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self.rows();
+        let stride = 1 + self.gap;
+        let wire_x = |wire: usize| area.x as usize + wire * stride as usize;
+
+        // Keep the current layer in the top third of the view
+        let first_current = rows
+            .iter()
+            .position(|(layer, _)| *layer >= self.current)
+            .unwrap_or(rows.len());
+        let height = area.height as usize;
+        let start = first_current
+            .saturating_sub(height / 3)
+            .min(rows.len().saturating_sub(height));
+
+        for (y, (layer, comparators)) in rows.iter().skip(start).take(height).enumerate() {
+            let y = area.y + y as u16;
+            for wire in 0..self.len {
+                let x = wire_x(wire);
+                if x < area.right() as usize {
+                    buf.get_mut(x as u16, y)
+                        .set_symbol("│")
+                        .set_style(Style::default().fg(self.theme.muted));
+                }
+            }
+
+            let style = if self.sorted || *layer < self.current {
+                Style::default().fg(self.theme.complete)
+            } else if *layer == self.current {
+                Style::default()
+                    .fg(self.theme.current)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.label)
+            };
+            for &(i, j) in comparators {
+                for x in wire_x(i)..=wire_x(j).min(area.right() as usize - 1) {
+                    let symbol = if x == wire_x(i) || x == wire_x(j) {
+                        "●"
+                    } else {
+                        "─"
+                    };
+                    buf.get_mut(x as u16, y).set_symbol(symbol).set_style(style);
+                }
+            }
+        }
+    }
+}
+
+pub fn draw_wire_diagram(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    sort_iter: &dyn sort::Sort,
+    block: Block,
+) {
+    let inner = block.inner(chunk);
+    f.render_widget(block, chunk);
+
+    if let Some(network) = sort_iter.network() {
+        // The latest snapshot shows the layer that was just applied
+        let current = sort_iter.get_current_step().saturating_sub(1);
+        f.render_widget(
+            WireDiagram {
+                theme,
+                len: sort_iter.items().len(),
+                network,
+                current,
+                sorted: sort_iter.is_sorted(),
+                gap: bar_gap(inner, sort_iter.items().len()),
+            },
+            inner,
         );
-        draw_sort(f, chunks[2], sort);
-        draw_sort_footer(f, chunks[3], sort.is_active(), sort.is_sorted());
     }
 }
+
+/// Marks where the regions of a partition begin, with a line in the gap to
+/// the left of each boundary bar, or under it when bars have no gaps.
+struct Boundaries<'a> {
+    theme: &'a Theme,
+    positions: &'a [usize],
+    gap: u16,
+}
+
+impl<'a> Widget for Boundaries<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let style = Style::default().fg(self.theme.wire);
+        for &position in self.positions {
+            let x = area.x as usize + position * (1 + self.gap as usize);
+            if self.gap > 0 && position > 0 && x <= area.right() as usize {
+                for y in area.top()..area.bottom().saturating_sub(1) {
+                    buf.get_mut(x as u16 - 1, y)
+                        .set_symbol("┊")
+                        .set_style(style);
+                }
+            } else if self.gap == 0 && x < area.right() as usize {
+                buf.get_mut(x as u16, area.bottom() - 1)
+                    .set_symbol("▏")
+                    .set_style(style);
+            }
+        }
+    }
+}
+
+/// Renders a widget, then restyles regions of the area it was drawn into.
+/// With `patterns` set, full blocks in a region are also redrawn in the
+/// region's shade.
+struct Highlighted<W: Widget> {
+    widget: W,
+    regions: Vec<(Rect, Style, &'static str)>,
+    patterns: bool,
+}
+
+impl<W: Widget> Widget for Highlighted<W> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.widget.render(area, buf);
+        // Regions of bars that did not fit are left out, as they lie past
+        // the edge of the area
+        for (region, style, shade) in self.regions {
+            if !region.intersects(area) {
+                continue;
+            }
+            let region = region.intersection(area);
+            buf.set_style(region, style);
+            if !self.patterns {
+                continue;
+            }
+            for y in region.top()..region.bottom() {
+                for x in region.left()..region.right() {
+                    let cell = buf.get_mut(x, y);
+                    if cell.symbol == symbols::block::FULL {
+                        cell.set_symbol(shade);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Bars are separated by a single column unless that would push some of
+/// them out of the chart.
+fn bar_gap(inner: Rect, count: usize) -> u16 {
+    if inner.width as usize >= count * 2 {
+        1
+    } else {
+        0
+    }
+}
+
+/// How many of `count` bars the chart has room for. The bar chart stops
+/// drawing at the first one that would not fit.
+fn bars_drawn(inner: Rect, bar_width: u16, gap: u16, count: usize) -> usize {
+    (inner.width / (bar_width + gap)).min(count as u16) as usize
+}
+
+/// The columns covering the bars in `lo..hi`, excluding the label row.
+fn bar_region(inner: Rect, bar_width: u16, gap: u16, lo: usize, hi: usize) -> Rect {
+    let stride = bar_width + gap;
+    Rect {
+        x: inner.x + lo as u16 * stride,
+        y: inner.y,
+        width: ((hi - lo) as u16 * stride).saturating_sub(gap),
+        height: inner.height.saturating_sub(1),
+    }
+}
+
+fn current_subrange(sort_iter: &dyn sort::Sort) -> Option<(usize, usize)> {
+    if sort_iter.is_sorted() {
+        return None;
+    }
+    sort_iter
+        .call_stack()
+        .current()
+        .map(|frame| (frame.lo, frame.hi))
+}
+
+/// The range flipped during the step, for sorts that reverse whole ranges.
+fn current_reversal(sort_iter: &dyn sort::Sort) -> Option<(usize, usize)> {
+    if sort_iter.is_sorted() {
+        return None;
+    }
+    sort_iter
+        .get_snapshot()
+        .operations
+        .iter()
+        .find_map(|operation| match operation {
+            sort::Operation::Reverse(lo, hi) => Some((*lo, *hi)),
+            _ => None,
+        })
+}
+
+/// For gapped sorts, the elements `gap` apart from the pointer that are being
+/// sorted as one subsequence, among the first `drawn`.
+fn current_subsequence(sort_iter: &dyn sort::Sort, drawn: usize) -> impl Iterator<Item = usize> {
+    let gap = sort_iter
+        .gap()
+        .filter(|&gap| gap > 1 && !sort_iter.is_sorted());
+    let start = gap.map_or(0, |gap| sort_iter.get_pointer().0 % gap);
+    let len = gap.map_or(0, |_| drawn.min(sort_iter.items().len()));
+    (start..len).step_by(gap.unwrap_or(1))
+}
+
+const AUX_ARRAY_HEIGHT: u16 = 7;
+
+pub fn draw_aux_array(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    aux: &sort::AuxArray,
+) {
+    let data: Vec<(&'static str, u64)> = aux
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let value = x.unwrap_or_default() as u64;
+            if aux.write == Some(i) || aux.read == Some(i) {
+                ("⬆", value)
+            } else {
+                ("", value)
+            }
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(aux.name)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.label));
+    let gap = bar_gap(block.inner(chunk), data.len());
+
+    let chart = BarChart::default()
+        .block(block)
+        .data(&data)
+        .max(100)
+        .bar_width(1)
+        .bar_gap(gap)
+        .bar_style(Style::default().fg(theme.aux))
+        .label_style(Style::default().fg(theme.compared));
+
+    f.render_widget(chart, chunk);
+}
+
+/// Shows the piles side by side with the bottom card first, keeping the top
+/// cards in view when a pile outgrows the panel.
+pub fn draw_piles(f: &mut Frame<impl Backend>, chunk: Rect, theme: &Theme, piles: &sort::Piles) {
+    let block = Block::default()
+        .title(format!("Piles ({})", piles.piles.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.label));
+    let height = block.inner(chunk).height as usize;
+
+    let text: Vec<Spans> = (0..height)
+        .map(|row| {
+            Spans::from(
+                piles
+                    .piles
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pile)| {
+                        let index = pile.len().saturating_sub(height) + row;
+                        let mut style = if piles.current == Some(i) {
+                            Style::default().fg(theme.current)
+                        } else {
+                            Style::default().fg(theme.label)
+                        };
+                        if index + 1 == pile.len() {
+                            style = style.add_modifier(Modifier::BOLD);
+                        }
+                        match pile.get(index) {
+                            Some(value) => Span::styled(format!("{:>3}", value), style),
+                            None => Span::raw("   "),
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(text).block(block), chunk);
+}
+
+/// Draws bucket counts, merging neighbouring buckets when there are more of
+/// them than columns to draw them in.
+pub fn draw_histogram(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    histogram: &sort::Histogram,
+) {
+    let block = Block::default()
+        .title(histogram.name.as_str())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.label));
+    let inner = block.inner(chunk);
+
+    let group = (histogram.counts.len() as f64 / inner.width.max(1) as f64).ceil() as usize;
+    let group = group.max(1);
+    let counts: Vec<u64> = histogram
+        .counts
+        .chunks(group)
+        .map(|counts| counts.iter().sum::<usize>() as u64)
+        .collect();
+    let labels: Vec<String> = (0..counts.len())
+        .map(|i| match char::from_digit(i as u32, 36) {
+            Some(digit) if group == 1 => digit.to_string(),
+            _ => String::new(),
+        })
+        .collect();
+    let data: Vec<(&str, u64)> = labels
+        .iter()
+        .map(String::as_str)
+        .zip(counts.iter().cloned())
+        .collect();
+
+    let gap = bar_gap(inner, data.len());
+    let bar_width = (inner.width / data.len().max(1) as u16)
+        .saturating_sub(gap)
+        .max(1);
+    let regions = histogram
+        .current
+        .map(|bucket| {
+            let bucket = bucket / group;
+            vec![(
+                bar_region(inner, bar_width, gap, bucket, bucket + 1),
+                Style::default().fg(theme.compared),
+                "▒",
+            )]
+        })
+        .unwrap_or_default();
+
+    let chart = BarChart::default()
+        .block(block)
+        .data(&data)
+        .bar_width(bar_width)
+        .bar_gap(gap)
+        .bar_style(Style::default().fg(theme.histogram))
+        .value_style(Style::default().fg(theme.background).bg(theme.histogram))
+        .label_style(Style::default().fg(theme.label));
+
+    f.render_widget(
+        Highlighted {
+            widget: chart,
+            regions,
+            patterns: theme.patterns,
+        },
+        chunk,
+    );
+}
+
+fn is_highlighted(sort_iter: &dyn sort::Sort, index: usize) -> bool {
+    let p = sort_iter.get_pointer();
+    let compared = sort_iter
+        .comparators()
+        .iter()
+        .any(|&(i, j)| i == index || j == index);
+    !sort_iter.is_sorted() && (p.0 == index || p.1 == index || compared)
+}
+
+/// Maps a value in the `0..100` data range onto a hue between red and violet.
+fn value_color(value: f64) -> Color {
+    let hue = (value / 100.0).clamp(0.0, 1.0) * 300.0;
+    let x = 1.0 - ((hue / 60.0) % 2.0 - 1.0).abs();
+    let (r, g, b) = match (hue / 60.0) as u8 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    Color::Rgb((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8)
+}
+
+/// Stands in for `value_color` under a patterned theme, with denser shades
+/// for larger values.
+fn value_shade(value: f64) -> &'static str {
+    const SHADES: [&str; 5] = [" ", "░", "▒", "▓", "█"];
+    SHADES[((value / 100.0).clamp(0.0, 1.0) * 4.0).round() as usize]
+}
+
+pub fn draw_bars(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    sort_iter: &dyn sort::Sort,
+    block: Block,
+    bar_style: Style,
+    inspected: Option<usize>,
+) -> (Rect, u16) {
+    let data: Vec<(&'static str, u64)> = sort_iter
+        .items()
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            if is_highlighted(sort_iter, i) {
+                ("⬆", *x as u64)
+            } else {
+                ("", *x as u64)
+            }
+        })
+        .collect();
+
+    let inner = block.inner(chunk);
+    let gap = bar_gap(inner, data.len());
+    let mut regions: Vec<(Rect, Style, &str)> = current_subrange(sort_iter)
+        .map(|(lo, hi)| {
+            vec![(
+                bar_region(inner, 1, gap, lo, hi),
+                Style::default().fg(theme.subrange),
+                "▒",
+            )]
+        })
+        .unwrap_or_default();
+    if !sort_iter.is_sorted() {
+        regions.extend(sort_iter.pivots().iter().map(|&pivot| {
+            (
+                bar_region(inner, 1, gap, pivot, pivot + 1),
+                Style::default().fg(theme.pivot),
+                "░",
+            )
+        }));
+    }
+    regions.extend(current_reversal(sort_iter).map(|(lo, hi)| {
+        (
+            bar_region(inner, 1, gap, lo, hi),
+            Style::default().fg(theme.reversal),
+            "▚",
+        )
+    }));
+    let drawn = bars_drawn(inner, 1, gap, data.len());
+    regions.extend(current_subsequence(sort_iter, drawn).map(|index| {
+        (
+            bar_region(inner, 1, gap, index, index + 1),
+            Style::default().fg(theme.subrange),
+            "▒",
+        )
+    }));
+    regions.extend(inspected.filter(|index| *index < data.len()).map(|index| {
+        (
+            bar_region(inner, 1, gap, index, index + 1),
+            Style::default().fg(theme.current),
+            "▓",
+        )
+    }));
+
+    let chart = BarChart::default()
+        .block(block)
+        .data(&data)
+        .bar_width(1)
+        .bar_gap(gap)
+        .bar_style(bar_style)
+        .label_style(Style::default().fg(theme.compared));
+
+    f.render_widget(
+        Highlighted {
+            widget: chart,
+            regions,
+            patterns: theme.patterns,
+        },
+        chunk,
+    );
+    if !sort_iter.is_sorted() {
+        f.render_widget(
+            Boundaries {
+                theme,
+                positions: sort_iter.boundaries(),
+                gap,
+            },
+            inner,
+        );
+    }
+    (inner, gap)
+}
+
+pub fn draw_scatter(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    sort_iter: &dyn sort::Sort,
+    block: Block,
+    point_style: Style,
+) {
+    let items = sort_iter.items();
+    let subrange = current_subrange(sort_iter);
+    let mut points = vec![];
+    let mut in_subrange = vec![];
+    let mut highlighted = vec![];
+    for (i, x) in items.iter().enumerate() {
+        if is_highlighted(sort_iter, i) {
+            highlighted.push((i as f64, *x));
+        } else if subrange.is_some_and(|(lo, hi)| lo <= i && i < hi) {
+            in_subrange.push((i as f64, *x));
+        } else {
+            points.push((i as f64, *x));
+        }
+    }
+
+    let datasets = vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Scatter)
+            .style(point_style)
+            .data(&points),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(theme.subrange))
+            .data(&in_subrange),
+        Dataset::default()
+            .marker(symbols::Marker::Block)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(theme.compared))
+            .data(&highlighted),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(block)
+        .x_axis(Axis::default().bounds([0.0, items.len() as f64]))
+        .y_axis(Axis::default().bounds([0.0, 100.0]));
+
+    f.render_widget(chart, chunk);
+}
+
+pub fn draw_hue_strip(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    sort_iter: &dyn sort::Sort,
+    block: Block,
+) {
+    let items = sort_iter.items();
+    let inner = block.inner(chunk);
+    let column_width = (inner.width as usize / items.len().max(1)).max(1);
+    let rows = inner.height.saturating_sub(1) as usize;
+
+    let strip = Spans::from(
+        items
+            .iter()
+            .map(|x| {
+                if theme.patterns {
+                    Span::styled(
+                        value_shade(*x).repeat(column_width),
+                        Style::default().fg(theme.text),
+                    )
+                } else {
+                    Span::styled(
+                        "█".repeat(column_width),
+                        Style::default().fg(value_color(*x)),
+                    )
+                }
+            })
+            .collect::<Vec<Span>>(),
+    );
+    let markers = Spans::from(
+        (0..items.len())
+            .map(|i| {
+                let marker = if is_highlighted(sort_iter, i) {
+                    "⬆"
+                } else {
+                    " "
+                };
+                Span::styled(
+                    format!("{:<1$}", marker, column_width),
+                    Style::default().fg(theme.compared),
+                )
+            })
+            .collect::<Vec<Span>>(),
+    );
+
+    let mut text = vec![strip; rows];
+    text.push(markers);
+
+    f.render_widget(Paragraph::new(text).block(block), chunk);
+}
+
+pub fn draw_disparity_circle(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    sort_iter: &dyn sort::Sort,
+    block: Block,
+) {
+    let items = sort_iter.items();
+    let positions = sort::final_positions(items);
+    let half = (items.len() as f64 / 2.0).max(1.0);
+
+    // The canvas cannot be drawn into an area with no cells at all
+    let inner = block.inner(chunk);
+    if inner.width == 0 || inner.height == 0 {
+        f.render_widget(block, chunk);
+        return;
+    }
+
+    // Terminal cells are roughly twice as tall as they are wide, so widen the
+    // horizontal bounds to keep the circle round.
+    let aspect = inner.width as f64 / (inner.height as f64 * 2.0).max(1.0);
+
+    let dots: Vec<((f64, f64), Color, bool)> = items
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let displacement = (i as f64 - positions[i] as f64).abs();
+            let radius = (displacement / half).min(1.0);
+            let angle = std::f64::consts::TAU * i as f64 / items.len() as f64;
+            let highlighted = is_highlighted(sort_iter, i);
+            let color = if highlighted || theme.patterns {
+                theme.text
+            } else {
+                value_color(*x)
+            };
+            let coords = (radius * angle.sin(), radius * angle.cos());
+            (coords, color, highlighted)
+        })
+        .collect();
+
+    let canvas = Canvas::default()
+        .block(block)
+        .marker(symbols::Marker::Block)
+        .x_bounds([-1.1 * aspect, 1.1 * aspect])
+        .y_bounds([-1.1, 1.1])
+        .paint(|ctx| {
+            for (coords, color, highlighted) in &dots {
+                // Without colour, the items in play get a glyph of their own
+                if *highlighted && theme.patterns {
+                    ctx.print(coords.0, coords.1, "◆");
+                } else {
+                    ctx.draw(&Points {
+                        coords: &[*coords],
+                        color: *color,
+                    });
+                }
+            }
+        });
+
+    f.render_widget(canvas, chunk);
+}
+
+/// `key: text` for the key bound to `action`.
+fn key_hint(keymap: &Keymap, action: Action, text: &str) -> String {
+    format!("{}: {}", keymap::key_name(keymap.key(action)), text)
+}
+
+pub fn draw_menu_footer(f: &mut Frame<impl Backend>, chunk: Rect, theme: &Theme, keymap: &Keymap) {
+    let title = [
+        key_hint(keymap, Action::Quit, "quit").replacen(':', "/Ctrl-c:", 1),
+        key_hint(keymap, Action::Select, "select sort"),
+        key_hint(keymap, Action::Info, "info"),
+        key_hint(keymap, Action::Settings, "settings"),
+        key_hint(keymap, Action::Help, "all keys"),
+    ]
+    .join(", ");
+    let block = Block::default()
+        .title(title)
+        .style(Style::default().fg(theme.footer).bg(Color::Reset));
+
+    f.render_widget(block, chunk);
+}
+
+pub fn draw_sort_footer(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    keymap: &Keymap,
+    active: bool,
+    sorted: bool,
+) {
+    let start = if active {
+        "pause sort"
+    } else if sorted {
+        "restart sort"
+    } else {
+        "start sort"
+    };
+    let title = [
+        key_hint(keymap, Action::Start, start),
+        key_hint(keymap, Action::Render, "change view"),
+        key_hint(keymap, Action::Edit, "edit"),
+        key_hint(keymap, Action::Settings, "settings"),
+        key_hint(keymap, Action::Help, "all keys"),
+        key_hint(keymap, Action::Quit, "back to menu"),
+    ]
+    .join(", ");
+
+    let block = Block::default()
+        .title(title)
+        .style(Style::default().fg(theme.footer).bg(Color::Reset));
+
+    f.render_widget(block, chunk);
+}
+
+pub fn draw_edit_footer(f: &mut Frame<impl Backend>, chunk: Rect, theme: &Theme, keymap: &Keymap) {
+    let key = |action| keymap::key_name(keymap.key(action));
+    let title = [
+        format!("{}/{}: move", key(Action::Left), key(Action::Right)),
+        format!("{}/{}: value", key(Action::Raise), key(Action::Lower)),
+        key_hint(keymap, Action::Swap, "swap"),
+        key_hint(keymap, Action::Insert, "insert"),
+        key_hint(keymap, Action::Delete, "delete"),
+        key_hint(keymap, Action::Preset, "preset"),
+        key_hint(keymap, Action::Start, "start"),
+        key_hint(keymap, Action::Quit, "done"),
+    ]
+    .join(", ");
+    let block = Block::default()
+        .title(title)
+        .style(Style::default().fg(theme.footer).bg(Color::Reset));
+
+    f.render_widget(block, chunk);
+}
+
+const METRICS_HEIGHT: u16 = 3;
+
+/// Shows how sorted the array currently is, next to a sparkline of the
+/// inversion count at every step so far.
+pub fn draw_metrics(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    metrics: &Metrics,
+    history: &MetricsHistory,
+) {
+    let block = Block::default()
+        .title("Sortedness")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.label));
+    let inner = block.inner(chunk);
+    f.render_widget(block, chunk);
+
+    let text = format!(
+        "inv {} runs {} lis {} disp {:.1}",
+        metrics.inversions, metrics.runs, metrics.longest_increasing, metrics.mean_displacement
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Length(text.len() as u16 + 2),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(inner);
+    f.render_widget(Paragraph::new(text), chunks[0]);
+
+    // Squeeze the whole history into the space left by averaging buckets
+    let width = chunks[1].width.max(1) as usize;
+    let group = history.inversions.len().div_ceil(width).max(1);
+    let data: Vec<u64> = history
+        .inversions
+        .chunks(group)
+        .map(|values| values.iter().sum::<u64>() / values.len() as u64)
+        .collect();
+    let max = history.inversions.iter().copied().max().unwrap_or(0).max(1);
+    let sparkline = Sparkline::default()
+        .data(&data)
+        .max(max)
+        .style(Style::default().fg(theme.aux));
+    f.render_widget(sparkline, chunks[1]);
+}
+
+/// Lists the sort's own settings under the header with the keys that cycle
+/// them.
+pub fn draw_settings(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    keymap: &Keymap,
+    settings: &[sort::Setting],
+) {
+    let key = |name: &str| {
+        Action::ALL
+            .into_iter()
+            .find(|action| action.setting() == Some(name))
+            .map_or("?".to_string(), |action| {
+                keymap::key_name(keymap.key(action))
+            })
+    };
+    let text = settings
+        .iter()
+        .map(|setting| {
+            format!(
+                "{}: {} [{}]",
+                key(setting.name),
+                setting.name,
+                setting.value
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("   ");
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(theme.label))
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, chunk);
+}
+
+/// Shows why the sort stopped early in place of its settings.
+pub fn draw_error(f: &mut Frame<impl Backend>, chunk: Rect, theme: &Theme, error: &str) {
+    let paragraph = Paragraph::new(format!("Error: {}", error))
+        .style(Style::default().fg(theme.error))
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, chunk);
+}
+
+/// Shows the value of the bar clicked on, and where it started, in place of
+/// the sort's settings.
+pub fn draw_inspected(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    sort_iter: &dyn sort::Sort,
+    index: usize,
+) {
+    let items = sort_iter.items();
+    let mut text = format!("value {} at index {}", items[index], index);
+    if let Some(start) = sort::original_index(&sort_iter.stepper().input, items, index) {
+        text.push_str(&format!(", started at index {}", start));
+    }
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(theme.current))
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, chunk);
+}
+
+/// Shows the item under the edit cursor in place of the sort's settings.
+pub fn draw_edit_status(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    items: &[f64],
+    cursor: usize,
+) {
+    let text = format!(
+        "editing item {} of {}, value {}",
+        cursor + 1,
+        items.len(),
+        items[cursor]
+    );
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(theme.current))
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, chunk);
+}
+
+/// A track running from the first step to the last, with a marker at the
+/// current one. Returns the track, which can be clicked to seek.
+pub fn draw_timeline(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    step: usize,
+    total: usize,
+) -> Rect {
+    let count = format!(" {}/{}", step, total);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(count.len() as u16)].as_ref())
+        .split(chunk);
+    let track = chunks[0];
+
+    let width = track.width as usize;
+    let marker = match total {
+        0 => 0,
+        total => (step * width.saturating_sub(1) + total / 2) / total,
+    };
+    let line = Spans::from(vec![
+        Span::styled("━".repeat(marker), Style::default().fg(theme.current)),
+        Span::styled("●", Style::default().fg(theme.current)),
+        Span::styled(
+            "─".repeat(width.saturating_sub(marker + 1)),
+            Style::default().fg(theme.muted),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(line), track);
+    f.render_widget(
+        Paragraph::new(count).style(Style::default().fg(theme.label)),
+        chunks[1],
+    );
+    track
+}
+
+pub fn draw_single_sort(f: &mut Frame<impl Backend>, app: &mut App, chunk: Rect) {
+    let render_style = app.render_style;
+    let theme = &app.theme;
+    if let Some(sort) = app.sort.as_mut() {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints(
+                [
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Min(0),
+                    Constraint::Length(1),
+                    Constraint::Length(METRICS_HEIGHT),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .split(chunk);
+
+        draw_header(
+            f,
+            chunks[0],
+            theme,
+            sort.get_name(),
+            sort.is_active(),
+            sort.is_sorted(),
+        );
+        // While editing, the cursor is marked the way an inspected bar is
+        let editing = matches!(app.current_view, View::Edit);
+        let marked = match (editing, app.states.edit.as_ref()) {
+            (true, Some(edit)) => Some(edit.cursor),
+            _ => app.inspected,
+        };
+        let marked = marked.filter(|index| *index < sort.items().len());
+        match (sort.error(), marked) {
+            (Some(error), _) => draw_error(f, chunks[1], theme, &error),
+            (None, Some(index)) if editing => {
+                draw_edit_status(f, chunks[1], theme, sort.items(), index)
+            }
+            (None, Some(index)) => draw_inspected(f, chunks[1], theme, sort.as_ref(), index),
+            (None, None) => draw_settings(f, chunks[1], theme, &app.keymap, &sort.settings()),
+        }
+        app.areas.bars = draw_sort(f, chunks[2], theme, sort, render_style, marked);
+
+        // Inset to line up with the panels above
+        let timeline_chunk = Layout::default()
+            .horizontal_margin(1)
+            .constraints([Constraint::Min(0)].as_ref())
+            .split(chunks[3])[0];
+        let total = sort.total_steps();
+        app.areas.timeline =
+            draw_timeline(f, timeline_chunk, theme, sort.get_current_step(), total);
+
+        let metrics = Metrics::of(sort.items());
+        app.metrics_history
+            .update(sort.get_current_step(), metrics.inversions);
+        // Line the panel up with the sort panels, which sit inside a margin
+        let metrics_chunk = Layout::default()
+            .horizontal_margin(1)
+            .constraints([Constraint::Min(0)].as_ref())
+            .split(chunks[4])[0];
+        draw_metrics(f, metrics_chunk, theme, &metrics, &app.metrics_history);
+
+        if editing {
+            draw_edit_footer(f, chunks[5], theme, &app.keymap);
+        } else {
+            draw_sort_footer(
+                f,
+                chunks[5],
+                theme,
+                &app.keymap,
+                sort.is_active(),
+                sort.is_sorted(),
+            );
+        }
+    }
+}
+
+const COMPLEXITY_LIST_WIDTH: u16 = 44;
+fn series_color(theme: &Theme, index: usize) -> Color {
+    theme.series[index % theme.series.len()]
+}
+
+/// Without colour, the measured series are told apart by how their lines
+/// are drawn, and the list shows a sample of each beside its exponents.
+const SERIES_MARKERS: [(symbols::Marker, GraphType, &str); 3] = [
+    (symbols::Marker::Braille, GraphType::Line, " ⣀⠤"),
+    (symbols::Marker::Block, GraphType::Line, " ▄▄"),
+    (symbols::Marker::Block, GraphType::Scatter, " ▄ ▄"),
+];
+
+fn series_marker(theme: &Theme, position: usize) -> (symbols::Marker, GraphType, &'static str) {
+    if theme.patterns {
+        SERIES_MARKERS[position % SERIES_MARKERS.len()]
+    } else {
+        (symbols::Marker::Braille, GraphType::Line, "")
+    }
+}
+
+fn format_exponent(exponent: Option<f64>) -> String {
+    exponent.map_or("  -".to_string(), |k| format!("{:.2}", k))
+}
+
+/// Lists the algorithms with checkboxes, and for each measured one the
+/// exponents fitted to its comparisons and swaps.
+pub fn draw_complexity_list(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    state: &mut crate::app::ComplexityState,
+) {
+    let items: Vec<ListItem> = state
+        .list
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, algorithm)| {
+            let name = algorithm.name;
+            let check = if state.chosen[i] { "[x]" } else { "[ ]" };
+            let position = state.results.iter().position(|series| series.index == i);
+            let (text, style) = match position {
+                Some(position) => {
+                    let series = &state.results[position];
+                    let (_, _, sample) = series_marker(theme, position);
+                    (
+                        format!(
+                            "{} {:<22} {} {}{}",
+                            check,
+                            name,
+                            format_exponent(complexity::fit_exponent(&series.comparisons())),
+                            format_exponent(complexity::fit_exponent(&series.swaps())),
+                            sample,
+                        ),
+                        Style::default().fg(series_color(theme, i)),
+                    )
+                }
+                None => (
+                    format!("{} {}", check, name),
+                    Style::default().fg(theme.text),
+                ),
+            };
+            ListItem::new(text).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("Algorithms (cmp swp exponents)")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.label)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::ITALIC | Modifier::BOLD))
+        .highlight_symbol(">");
+
+    f.render_stateful_widget(list, chunk, &mut state.list.state);
+}
+
+/// Plots one operation count of every measured algorithm against n, over
+/// faint reference curves for n, n log n and n².
+pub fn draw_complexity_chart(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    title: &str,
+    series: &[(usize, Vec<(f64, f64)>)],
+    max_size: usize,
+) {
+    let max_n = max_size as f64;
+    let max_y = series
+        .iter()
+        .flat_map(|(_, points)| points.iter().map(|(_, y)| *y))
+        .fold(max_n, f64::max);
+
+    // Sample the reference curves finely and stop them at the top of the
+    // chart, so they stay visible as lines rather than running off it
+    let references: Vec<(&str, Vec<(f64, f64)>)> = [
+        ("n", (|n: f64| n) as fn(f64) -> f64),
+        ("n log n", |n: f64| n * n.log2()),
+        ("n²", |n: f64| n * n),
+    ]
+    .into_iter()
+    .map(|(name, curve)| {
+        let points = (1..=max_size)
+            .map(|n| (n as f64, curve(n as f64)))
+            .take_while(|(_, y)| *y <= max_y)
+            .collect();
+        (name, points)
+    })
+    .collect();
+
+    let mut datasets: Vec<Dataset> = references
+        .iter()
+        .map(|(name, points)| {
+            Dataset::default()
+                .name(*name)
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.muted))
+                .data(points)
+        })
+        .collect();
+    datasets.extend(
+        series
+            .iter()
+            .enumerate()
+            .map(|(position, (index, points))| {
+                let (marker, graph_type, _) = series_marker(theme, position);
+                Dataset::default()
+                    .marker(marker)
+                    .graph_type(graph_type)
+                    .style(Style::default().fg(series_color(theme, *index)))
+                    .data(points)
+            }),
+    );
+
+    let label_style = Style::default().fg(theme.label);
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(title.to_string())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.label)),
+        )
+        .x_axis(Axis::default().bounds([0.0, max_n]).labels(vec![
+            Span::styled("0", label_style),
+            Span::styled(max_size.to_string(), label_style),
+        ]))
+        .y_axis(Axis::default().bounds([0.0, max_y]).labels(vec![
+            Span::styled("0", label_style),
+            Span::styled(format!("{}", max_y as u64), label_style),
+        ]));
+
+    f.render_widget(chart, chunk);
+}
+
+pub fn draw_complexity_footer(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    keymap: &Keymap,
+    max_size: usize,
+) {
+    let title = [
+        key_hint(keymap, Action::Choose, "toggle"),
+        key_hint(keymap, Action::Run, "run"),
+        format!(
+            "{}/{}: max size {}",
+            keymap::key_name(keymap.key(Action::Grow)),
+            keymap::key_name(keymap.key(Action::Shrink)),
+            max_size
+        ),
+        key_hint(keymap, Action::Help, "all keys"),
+        key_hint(keymap, Action::Quit, "back to menu"),
+    ]
+    .join(", ");
+    let block = Block::default()
+        .title(title)
+        .style(Style::default().fg(theme.footer).bg(Color::Reset));
+
+    f.render_widget(block, chunk);
+}
+
+pub fn draw_complexity(f: &mut Frame<impl Backend>, app: &mut App, chunk: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(chunk);
+
+    let theme = &app.theme;
+    let state = app.states.complexity.as_mut().unwrap();
+    draw_header(
+        f,
+        chunks[0],
+        theme,
+        "Empirical Complexity".to_string(),
+        state.measurement.is_some(),
+        false,
+    );
+    if let Some(measurement) = &state.measurement {
+        let done = measurement.total - measurement.pending.len();
+        let status = format!(
+            "measuring {} of {}: {}",
+            done + 1,
+            measurement.total,
+            measurement.pending.first().unwrap_or(&"")
+        );
+        f.render_widget(
+            Paragraph::new(status)
+                .style(Style::default().fg(theme.sorting))
+                .alignment(Alignment::Center),
+            chunks[1],
+        );
+    }
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Length(COMPLEXITY_LIST_WIDTH),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(chunks[2]);
+    draw_complexity_list(f, body[0], theme, state);
+
+    let charts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(body[1]);
+    let comparisons: Vec<(usize, Vec<(f64, f64)>)> = state
+        .results
+        .iter()
+        .map(|series| (series.index, series.comparisons()))
+        .collect();
+    let swaps: Vec<(usize, Vec<(f64, f64)>)> = state
+        .results
+        .iter()
+        .map(|series| (series.index, series.swaps()))
+        .collect();
+    draw_complexity_chart(
+        f,
+        charts[0],
+        theme,
+        "Comparisons vs n",
+        &comparisons,
+        state.max_size,
+    );
+    draw_complexity_chart(f, charts[1], theme, "Swaps vs n", &swaps, state.max_size);
+
+    draw_complexity_footer(f, chunks[3], theme, &app.keymap, state.max_size);
+}
+
+/// Lists every setting with its current value, for the highlighted one to be
+/// stepped through its values.
+pub fn draw_settings_list(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    values: &[(SettingsField, String)],
+    state: &mut ListState,
+) {
+    let items: Vec<ListItem> = values
+        .iter()
+        .map(|(field, value)| ListItem::new(format!("{:<14} ‹ {} ›", field.get_name(), value)))
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.label)),
+        )
+        .style(Style::default().fg(theme.text))
+        .highlight_style(
+            Style::default()
+                .fg(theme.compared)
+                .add_modifier(Modifier::ITALIC),
+        )
+        .highlight_symbol(">>");
+
+    f.render_stateful_widget(list, chunk, state);
+}
+
+pub fn draw_settings_footer(
+    f: &mut Frame<impl Backend>,
+    chunk: Rect,
+    theme: &Theme,
+    keymap: &Keymap,
+) {
+    let title = [
+        format!(
+            "{}/{}: move",
+            keymap::key_name(keymap.key(Action::Up)),
+            keymap::key_name(keymap.key(Action::Down))
+        ),
+        format!(
+            "{}/{}: change",
+            keymap::key_name(keymap.key(Action::Decrease)),
+            keymap::key_name(keymap.key(Action::Increase))
+        ),
+        key_hint(keymap, Action::Help, "all keys"),
+        key_hint(keymap, Action::Quit, "back"),
+    ]
+    .join(", ");
+    let block = Block::default()
+        .title(title)
+        .style(Style::default().fg(theme.footer).bg(Color::Reset));
+
+    f.render_widget(block, chunk);
+}
+
+pub fn draw_settings_view(f: &mut Frame<impl Backend>, app: &mut App, chunk: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(chunk);
+
+    let theme = &app.theme;
+    draw_header(f, chunks[0], theme, "Settings".to_string(), false, false);
+    f.render_widget(
+        Paragraph::new("Changing the size, distribution or seed restarts the sort on new items")
+            .style(Style::default().fg(theme.label)),
+        chunks[1],
+    );
+
+    let values: Vec<(SettingsField, String)> = SettingsField::ALL
+        .iter()
+        .map(|field| (*field, app.setting_value(*field)))
+        .collect();
+    let settings = app.states.settings.as_mut().unwrap();
+    draw_settings_list(f, chunks[2], theme, &values, &mut settings.list.state);
+
+    draw_settings_footer(f, chunks[3], theme, &app.keymap);
+}
+
+const HELP_WIDTH: u16 = 46;
+
+/// A popup over the current view listing every key it responds to, read from
+/// the same keymap the input handlers look keys up in.
+pub fn draw_help(f: &mut Frame<impl Backend>, app: &App, chunk: Rect) {
+    let theme = &app.theme;
+    let row = |key: String, text: String| {
+        Spans::from(vec![
+            Span::styled(
+                format!("{:>10}  ", key),
+                Style::default().fg(theme.compared),
+            ),
+            Span::styled(text, Style::default().fg(theme.text)),
+        ])
+    };
+    // Setting actions are only listed for the sorts that have the setting
+    let settings: Vec<&str> = app.sort.as_ref().map_or(vec![], |sort| {
+        sort.settings().iter().map(|setting| setting.name).collect()
+    });
+    let mut text: Vec<Spans> = app
+        .current_view
+        .actions()
+        .iter()
+        .filter(|action| action.setting().is_none_or(|name| settings.contains(&name)))
+        .map(|action| {
+            row(
+                keymap::key_name(app.keymap.key(*action)),
+                action.describe().to_string(),
+            )
+        })
+        .collect();
+    match app.current_view {
+        View::Menu => {
+            text.push(row(
+                "click".to_string(),
+                "select, again to open".to_string(),
+            ));
+            text.push(row("wheel".to_string(), "move".to_string()));
+        }
+        View::Sort(_) | View::Script(_) => {
+            text.push(row(
+                "click".to_string(),
+                "inspect a bar, or seek".to_string(),
+            ));
+            text.push(row(
+                "drag".to_string(),
+                "seek along the timeline".to_string(),
+            ));
+            text.push(row("wheel".to_string(), "faster/slower".to_string()));
+        }
+        View::Edit => text.push(row("click".to_string(), "move to a bar".to_string())),
+        _ => (),
+    }
+    text.push(row("Ctrl-c".to_string(), "quit".to_string()));
+
+    let width = HELP_WIDTH.min(chunk.width);
+    let height = (text.len() as u16 + 2).min(chunk.height);
+    let area = Rect {
+        x: chunk.x + (chunk.width - width) / 2,
+        y: chunk.y + (chunk.height - height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .title("Keys (any key to close)")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.footer));
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(text).block(block), area);
+}